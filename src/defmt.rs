@@ -0,0 +1,62 @@
+//! [`defmt::Format`] implementations mirroring the `Debug` output, enabled by
+//! the `defmt` feature.
+use ::defmt::{write, Format, Formatter};
+
+use crate::{env::cause_name, Cause, Instruction, Register, Xlen};
+
+impl Format for Instruction {
+    fn format(&self, f: Formatter) {
+        if self.compressed() {
+            write!(f,
+                "Instruction {{ value: Compressed({=u16:#06x}), opcode: {=u8:#04b}, funct2: {=u8:#04b}, funct3: {=u8:#05b}, \
+                funct4: {=u8:#06b}, funct6: {=u8:#08b}, rd_rs1: {}, rs2: {}, crd_crs1': {}, crd_crs2': {} }}",
+                self.0 as u16,
+                self.op(),
+                self.compressed_funct2(),
+                self.compressed_funct3(),
+                self.compressed_funct4(),
+                self.compressed_funct6(),
+                self.compressed_rs1(),
+                self.compressed_rs2(),
+                self.crs1(),
+                self.crs2()
+            )
+        } else {
+            write!(f,
+                "Instruction {{ value: Standard({=u32:#010x}), opcode: {=u8:#09b}, funct3: {=u8:#05b}, funct7: {=u8:#09b}, \
+                rd: {}, rs1: {}, rs2: {}, i_immediate: {=u32:#010x}, s_immediate: {=u32:#010x}, \
+                b_immediate: {=u32:#010x}, u_immediate: {=u32:#010x}, j_immediate: {=u32:#010x} }}",
+                self.0,
+                self.opcode(),
+                self.funct3(),
+                self.funct7(),
+                self.rd(),
+                self.rs1(),
+                self.rs2(),
+                self.i_immediate(),
+                self.s_immediate(),
+                self.b_immediate(),
+                self.u_immediate(),
+                self.j_immediate()
+            )
+        }
+    }
+}
+
+/// Formats the register number followed by its ABI name, as the alternate
+/// `Debug` form does.
+impl Format for Register {
+    fn format(&self, f: Formatter) {
+        write!(f, "x{=u8} ({=str})", *self as u8, self.abi_name())
+    }
+}
+
+impl<X: Xlen> Format for Cause<X> {
+    fn format(&self, f: Formatter) {
+        let code = self.code().to_u64();
+        match cause_name(self.interrupt(), code) {
+            Some(name) => write!(f, "Cause::{=str}", name),
+            None => write!(f, "Cause(code={=u64}, interrupt={=bool})", code, self.interrupt())
+        }
+    }
+}
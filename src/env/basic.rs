@@ -0,0 +1,91 @@
+use crate::{Register, Xlen};
+
+use super::{Addressable, Hart, Privilege};
+
+/// A hart backed by a plain register file, with `x0` hardwired to zero.
+///
+/// The hart starts and resets in M-mode.
+/// ```rust
+/// use rysk::{Addressable, Cause, Hart, Register, env::{BasicHart, Privilege}};
+///
+/// struct Bus;
+/// impl Addressable<u64> for Bus {
+///     fn read_u8(&self, _: u64) -> Result<u8, Cause<u64>> { Err(Cause::LOAD_FAULT) }
+///     fn write_u8(&self, _: u64, _: u8) -> Result<(), Cause<u64>> { Err(Cause::STORE_FAULT) }
+/// }
+///
+/// let mut hart = BasicHart::new(Bus, 0x8000_0000u64);
+/// hart.store_register(Register::X0, 1);
+/// hart.store_register(Register::X10, 2);
+/// assert_eq!(hart.load_register(Register::X0), 0);
+/// assert_eq!(hart.load_register(Register::X10), 2);
+/// assert_eq!(hart.pc(), 0x8000_0000);
+/// assert_eq!(hart.privilege(), Privilege::Machine);
+///
+/// hart.set_privilege(Privilege::User);
+/// hart.reset(0x1000);
+/// assert_eq!(hart.load_register(Register::X10), 0);
+/// assert_eq!(hart.pc(), 0x1000);
+/// assert_eq!(hart.privilege(), Privilege::Machine);
+/// ```
+pub struct BasicHart<X: Xlen, A: Addressable<X>> {
+    regs: [X; 32],
+    pc: X,
+    privilege: Privilege,
+    bus: A
+}
+impl<X: Xlen, A: Addressable<X>> BasicHart<X, A> {
+    /// Create a hart attached to `bus` with every register zeroed, about to
+    /// fetch from `pc` in M-mode.
+    pub fn new(bus: A, pc: X) -> Self {
+        Self {
+            regs: [X::from_u64(0); 32],
+            pc,
+            privilege: Privilege::Machine,
+            bus
+        }
+    }
+    /// Zero every register, set the PC and return to M-mode, leaving the bus
+    /// untouched.
+    pub fn reset(&mut self, pc: X) {
+        self.regs = [X::from_u64(0); 32];
+        self.pc = pc;
+        self.privilege = Privilege::Machine;
+    }
+}
+impl<X: Xlen, A: Addressable<X>> Hart<X, A> for BasicHart<X, A> {
+    #[inline]
+    fn load_register(&self, r: Register) -> X {
+        self.regs[r]
+    }
+    #[inline]
+    fn store_register(&mut self, r: Register, v: X) {
+        if r != Register::X0 {
+            self.regs[r] = v
+        }
+    }
+    #[inline]
+    fn bus(&mut self) -> &mut A {
+        &mut self.bus
+    }
+    #[inline]
+    fn bus_ref(&self) -> &A {
+        &self.bus
+    }
+    #[inline]
+    fn pc(&self) -> X {
+        self.pc
+    }
+    #[inline]
+    fn set_pc(&mut self, pc: X) {
+        self.pc = pc
+    }
+    #[inline]
+    fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+    #[inline]
+    fn set_privilege(&mut self, p: Privilege) {
+        self.privilege = p
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{Register, Xlen};
+
+use super::{is_aligned, Addressable, Cause, Hart};
+
+/// Copy a device tree blob into memory at `address`, returning the address.
+///
+/// Operating systems such as Linux expect the blob to be 8-byte aligned, so
+/// any other address is rejected with [`Cause::STORE_MISALIGN`].
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use rysk::{Addressable, Cause, Hart, Register, env::{boot_arguments, load_dtb, BasicHart, Memory}};
+///
+/// let dtb = [0xD0, 0x0D, 0xFE, 0xED, 0x00, 0x00, 0x00, 0x38];
+/// let mut hart = BasicHart::new(Memory::new(0x8000_0000u32, 0x1000), 0x8000_0000);
+/// assert!(load_dtb(hart.bus(), 0x8000_0804, &dtb) == Err(Cause::STORE_MISALIGN));
+/// assert!(load_dtb(hart.bus(), 0x8000_0FF8, &dtb).is_ok());
+/// assert!(load_dtb(hart.bus(), 0x8000_1000, &dtb) == Err(Cause::STORE_FAULT));
+///
+/// let address = load_dtb(hart.bus(), 0x8000_0800, &dtb).ok().unwrap();
+/// let mut blob = [0; 8];
+/// hart.bus().read_bytes(address, &mut blob).ok().unwrap();
+/// assert_eq!(blob, dtb);
+///
+/// boot_arguments(&mut hart, 1, address);
+/// assert_eq!(hart.load_register(Register::X10), 1);
+/// assert_eq!(hart.load_register(Register::X11), 0x8000_0800);
+/// # }
+/// ```
+pub fn load_dtb<X: Xlen, A: Addressable<X>>(memory: &A, address: X, dtb: &[u8]) -> Result<X, Cause<X>> {
+    if !is_aligned(address, 8) {
+        return Err(Cause::STORE_MISALIGN);
+    }
+    memory.write_bytes(address, dtb)?;
+    Ok(address)
+}
+
+/// Set up the boot arguments a supervisor expects on entry, with the hart ID
+/// in `a0` and the device tree blob address in `a1`.
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use rysk::{Addressable, Hart, Register, env::{boot_arguments, load_dtb, BasicHart, SparseMemory}};
+///
+/// let mut hart = BasicHart::new(SparseMemory::new(), 0x8000_0000u64);
+/// let dtb = load_dtb(hart.bus(), 0x8220_0000, &[0xD0, 0x0D, 0xFE, 0xED]).ok().unwrap();
+/// boot_arguments(&mut hart, 0, dtb);
+/// assert_eq!(hart.load_register(Register::X10), 0);
+/// assert_eq!(hart.load_register(Register::X11), 0x8220_0000);
+/// assert_eq!(hart.bus().read_u32(0x8220_0000).ok(), Some(0xEDFE_0DD0));
+/// # }
+/// ```
+pub fn boot_arguments<X: Xlen, A: Addressable<X>, H: Hart<X, A>>(hart: &mut H, hartid: X, dtb: X) {
+    hart.store_register(Register::X10, hartid);
+    hart.store_register(Register::X11, dtb);
+}
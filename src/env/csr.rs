@@ -0,0 +1,101 @@
+use crate::Xlen;
+
+use super::{Addressable, Cause, Hart, Privilege};
+
+/// Access to the control and status registers of a hart.
+///
+/// Implementors provide the raw [`Csr::load_csr`] and [`Csr::store_csr`]
+/// hooks, while [`Csr::read_csr`] and [`Csr::write_csr`] enforce the
+/// permissions encoded in the CSR address. Bits 10..12 of the address are
+/// `0b11` for read-only registers and bits 8..10 give the lowest privilege
+/// level allowed to access it.
+/// ```rust
+/// use rysk::{Addressable, Cause, Hart, Register, env::{Csr, Privilege}};
+///
+/// # struct Bus;
+/// # impl Addressable<u32> for Bus {
+/// #     fn read_u8(&self, _: u32) -> Result<u8, Cause<u32>> { Err(Cause::LOAD_FAULT) }
+/// #     fn write_u8(&self, _: u32, _: u8) -> Result<(), Cause<u32>> { Err(Cause::STORE_FAULT) }
+/// # }
+/// struct Basic { mscratch: u32, privilege: Privilege, bus: Bus }
+/// # impl Hart<u32, Bus> for Basic {
+/// #     fn load_register(&self, _: Register) -> u32 { 0 }
+/// #     fn store_register(&mut self, _: Register, _: u32) {}
+/// #     fn bus(&mut self) -> &mut Bus { &mut self.bus }
+/// #     fn bus_ref(&self) -> &Bus { &self.bus }
+/// #     fn pc(&self) -> u32 { 0 }
+/// #     fn set_pc(&mut self, _: u32) {}
+/// #     fn privilege(&self) -> Privilege { self.privilege }
+/// #     fn set_privilege(&mut self, p: Privilege) { self.privilege = p }
+/// # }
+/// impl Csr<u32, Bus> for Basic {
+///     fn load_csr(&self, addr: u16) -> Result<u32, Cause<u32>> {
+///         match addr {
+///             0x340 => Ok(self.mscratch),
+///             // mhartid
+///             0xF14 => Ok(0),
+///             _ => Err(Cause::ILLEGAL_INSTRUCTION)
+///         }
+///     }
+///     fn store_csr(&mut self, addr: u16, value: u32) -> Result<(), Cause<u32>> {
+///         match addr {
+///             0x340 => self.mscratch = value,
+///             _ => return Err(Cause::ILLEGAL_INSTRUCTION)
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut hart = Basic { mscratch: 0, privilege: Privilege::Machine, bus: Bus };
+/// assert!(hart.write_csr(0x340, 42).is_ok());
+/// assert!(hart.read_csr(0x340) == Ok(42));
+/// assert!(hart.read_csr(0xF14) == Ok(0));
+/// // mhartid is read-only, so the write never reaches the hook.
+/// assert!(hart.write_csr(0xF14, 1) == Err(Cause::<u32>::ILLEGAL_INSTRUCTION));
+/// // Unimplemented
+/// assert!(hart.read_csr(0x341) == Err(Cause::<u32>::ILLEGAL_INSTRUCTION));
+/// // Machine-level registers are inaccessible from lower privilege levels.
+/// hart.set_privilege(Privilege::Supervisor);
+/// assert!(hart.read_csr(0x340) == Err(Cause::<u32>::ILLEGAL_INSTRUCTION));
+/// ```
+pub trait Csr<X: Xlen, A: Addressable<X>>: Hart<X, A> {
+    /// Read a CSR, returning an illegal instruction exception if it is
+    /// unimplemented.
+    ///
+    /// This is the raw hook, use [`Csr::read_csr`] to check permissions.
+    fn load_csr(&self, addr: u16) -> Result<X, Cause<X>>;
+    /// Write a CSR, returning an illegal instruction exception if it is
+    /// unimplemented.
+    ///
+    /// This is the raw hook, use [`Csr::write_csr`] to check permissions.
+    fn store_csr(&mut self, addr: u16, value: X) -> Result<(), Cause<X>>;
+
+    /// Read a CSR if the current privilege level may access it.
+    #[inline]
+    fn read_csr(&self, addr: u16) -> Result<X, Cause<X>> {
+        if !accessible(addr, self.privilege()) {
+            return Err(Cause::ILLEGAL_INSTRUCTION);
+        }
+        self.load_csr(addr)
+    }
+    /// Write a CSR if it is writable and the current privilege level may
+    /// access it.
+    #[inline]
+    fn write_csr(&mut self, addr: u16, value: X) -> Result<(), Cause<X>> {
+        if read_only(addr) || !accessible(addr, self.privilege()) {
+            return Err(Cause::ILLEGAL_INSTRUCTION);
+        }
+        self.store_csr(addr, value)
+    }
+}
+
+/// Returns `true` if the CSR address is in a read-only range.
+#[inline]
+fn read_only(addr: u16) -> bool {
+    (addr >> 10) & 0b11 == 0b11
+}
+/// Returns `true` if the CSR address may be accessed at `privilege`.
+#[inline]
+fn accessible(addr: u16, privilege: Privilege) -> bool {
+    (addr >> 8) & 0b11 <= privilege as u16
+}
@@ -1,9 +1,429 @@
-use crate::{Register, Xlen};
+use crate::{AluOp, BranchKind, Decoded, Instruction, LoadWidth, Register, StoreWidth, Xlen};
 
-use super::Addressable;
+use super::{Addressable, Cause, Privilege, TrapInfo};
 
 pub trait Hart<X: Xlen, A: Addressable<X>> {
+    /// Read a register from the register file.
+    ///
+    /// This is the raw hook, use [`Hart::get_reg`] to read `x0` as zero.
     fn load_register(&self, r: Register) -> X;
+    /// Write a register to the register file.
+    ///
+    /// This is the raw hook, use [`Hart::set_reg`] to discard writes to `x0`.
     fn store_register(&mut self, r: Register, v: X);
+    /// Return the bus the hart accesses memory through.
     fn bus(&mut self) -> &mut A;
-}
\ No newline at end of file
+    /// Return a shared reference to the bus, for read-only inspection.
+    ///
+    /// As the [`Addressable`] accesses take `&self` the bus can still be
+    /// written through, for example by a debugger.
+    fn bus_ref(&self) -> &A;
+    /// Return the address of the next instruction to be fetched.
+    ///
+    /// While an instruction executes the PC still holds its address, and after
+    /// a trap it holds the address of the faulting instruction.
+    fn pc(&self) -> X;
+    /// Set the address of the next instruction to be fetched.
+    ///
+    /// The address is not checked, a misaligned PC instead raises an
+    /// instruction address misaligned exception when next fetched.
+    fn set_pc(&mut self, pc: X);
+    /// Return the privilege level the hart is currently executing at.
+    fn privilege(&self) -> Privilege;
+    /// Change the privilege level the hart executes at.
+    fn set_privilege(&mut self, p: Privilege);
+
+    /// Read a register, with `x0` hardwired to zero.
+    /// ```rust
+    /// use rysk::{Addressable, Cause, Hart, Register, env::Privilege};
+    ///
+    /// struct Bus;
+    /// impl Addressable<u32> for Bus {
+    ///     fn read_u8(&self, _: u32) -> Result<u8, Cause<u32>> { Err(Cause::LOAD_FAULT) }
+    ///     fn write_u8(&self, _: u32, _: u8) -> Result<(), Cause<u32>> { Err(Cause::STORE_FAULT) }
+    /// }
+    /// struct Basic { regs: [u32; 32], pc: u32, bus: Bus }
+    /// impl Hart<u32, Bus> for Basic {
+    ///     fn load_register(&self, r: Register) -> u32 { self.regs[r] }
+    ///     fn store_register(&mut self, r: Register, v: u32) { self.regs[r] = v }
+    ///     fn bus(&mut self) -> &mut Bus { &mut self.bus }
+    ///     fn bus_ref(&self) -> &Bus { &self.bus }
+    ///     fn pc(&self) -> u32 { self.pc }
+    ///     fn set_pc(&mut self, pc: u32) { self.pc = pc }
+    ///     fn privilege(&self) -> Privilege { Privilege::Machine }
+    ///     fn set_privilege(&mut self, _: Privilege) {}
+    /// }
+    ///
+    /// let mut hart = Basic { regs: [0xFFFF_FFFF; 32], pc: 0, bus: Bus };
+    /// hart.set_reg(Register::X0, 42);
+    /// hart.set_reg(Register::X1, 42);
+    /// assert_eq!(hart.get_reg(Register::X0), 0);
+    /// assert_eq!(hart.get_reg(Register::X1), 42);
+    /// // The raw hooks are left untouched.
+    /// assert_eq!(hart.load_register(Register::X0), 0xFFFF_FFFF);
+    /// ```
+    #[inline]
+    fn get_reg(&self, r: Register) -> X {
+        if r == Register::X0 {
+            X::from_u64(0)
+        } else {
+            self.load_register(r)
+        }
+    }
+    /// Write a register, discarding writes to `x0`.
+    #[inline]
+    fn set_reg(&mut self, r: Register, v: X) {
+        if r != Register::X0 {
+            self.store_register(r, v)
+        }
+    }
+
+    /// Fetch the instruction at the PC, returning it along with whether it is
+    /// compressed.
+    ///
+    /// The second parcel is only read for standard instructions, so a
+    /// compressed instruction may end at the last mapped byte. A PC that is not
+    /// 16-bit aligned raises an instruction address misaligned exception and
+    /// faults reported by the bus are raised as instruction access faults. The
+    /// trap value holds the PC, or the address of the second parcel if only
+    /// that parcel faulted.
+    ///
+    /// Parcels are always read little-endian, whatever the
+    /// [bus endianness](Addressable::endianness).
+    /// ```rust
+    /// # #[cfg(feature = "alloc")] {
+    /// use rysk::{Addressable, Cause, Hart, env::{BasicHart, Endianness, Memory, TrapInfo}};
+    ///
+    /// // c.addi a0,-1 followed by the first parcel of addi a0,a1,4
+    /// let memory = Memory::from_vec(0x8000_0000u32, vec![0x7D, 0x15, 0x13, 0x85]);
+    /// let mut hart = BasicHart::new(memory, 0x8000_0000);
+    /// let (instruction, compressed) = hart.fetch().ok().unwrap();
+    /// assert!(compressed);
+    /// assert_eq!(instruction.raw(), 0x157D);
+    ///
+    /// // The standard instruction is cut short by the end of memory.
+    /// hart.set_pc(0x8000_0002);
+    /// assert!(hart.fetch() == Err(TrapInfo { cause: Cause::FETCH_FAULT, tval: 0x8000_0004 }));
+    /// hart.set_pc(0x8000_0001);
+    /// assert!(hart.fetch() == Err(TrapInfo { cause: Cause::FETCH_MISALIGN, tval: 0x8000_0001 }));
+    ///
+    /// // addi a0,a1,4
+    /// let memory = Memory::from_vec(0x1000u64, vec![0x13, 0x85, 0x45, 0x00]);
+    /// let mut hart = BasicHart::new(memory, 0x1000);
+    /// let (instruction, compressed) = hart.fetch().ok().unwrap();
+    /// assert!(!compressed);
+    /// assert_eq!(instruction.raw(), 0x00458513);
+    /// // Past the end of memory
+    /// hart.set_pc(0x1004);
+    /// assert!(hart.fetch() == Err(TrapInfo { cause: Cause::FETCH_FAULT, tval: 0x1004 }));
+    ///
+    /// // A big-endian data bus does not change the instruction byte order.
+    /// struct Big(Memory<u32>);
+    /// impl Addressable<u32> for Big {
+    ///     fn read_u8(&self, address: u32) -> Result<u8, Cause<u32>> { self.0.read_u8(address) }
+    ///     fn write_u8(&self, address: u32, byte: u8) -> Result<(), Cause<u32>> { self.0.write_u8(address, byte) }
+    ///     fn endianness(&self) -> Endianness { Endianness::Big }
+    /// }
+    /// let memory = Big(Memory::from_vec(0x1000, vec![0x13, 0x85, 0x45, 0x00]));
+    /// assert_eq!(memory.read_u16(0x1000).ok(), Some(0x1385));
+    /// let mut hart = BasicHart::new(memory, 0x1000);
+    /// let (instruction, compressed) = hart.fetch().ok().unwrap();
+    /// assert!(!compressed);
+    /// assert_eq!(instruction.raw(), 0x00458513);
+    /// # }
+    /// ```
+    fn fetch(&mut self) -> Result<(Instruction, bool), TrapInfo<X>> {
+        let pc = self.pc();
+        if pc.to_u64() & 1 != 0 {
+            return Err(TrapInfo { cause: Cause::FETCH_MISALIGN, tval: pc });
+        }
+        let low = parcel(self.bus(), pc).map_err(|_| TrapInfo { cause: Cause::FETCH_FAULT, tval: pc })?;
+        if low & 0b11 != 0b11 {
+            Ok(Instruction::from_parcels([low, 0]))
+        } else {
+            let tval = offset(pc, 2);
+            let high = parcel(self.bus(), tval).map_err(|_| TrapInfo { cause: Cause::FETCH_FAULT, tval })?;
+            Ok(Instruction::from_parcels([low, high]))
+        }
+    }
+
+    /// Fetch, decode and execute a single RV32I or compressed instruction at
+    /// the PC, advancing the PC on success.
+    ///
+    /// The instruction is read with [`Hart::fetch`]. On a trap the PC is left
+    /// pointing at the faulting instruction and no state is changed. `ecall`
+    /// raises an environment call from the current
+    /// [privilege level](Hart::privilege) while load and store faults are
+    /// propagated unchanged. The trap value is populated as described by
+    /// [`TrapInfo`]. Once the instruction retires [`Hart::on_retire`] is called
+    /// with its address. Registers are accessed through [`Hart::get_reg`] and
+    /// [`Hart::set_reg`].
+    ///
+    /// Compressed instructions are [expanded as RV32C](Instruction::expand_rv32),
+    /// so harts of any other XLEN raise an illegal instruction exception for
+    /// every compressed parcel rather than misinterpreting the RV64C encodings.
+    /// ```rust
+    /// # #[cfg(feature = "alloc")] {
+    /// use rysk::{Addressable, Cause, Hart, Instruction, Register, env::{assemble_into, BasicHart, Memory, Privilege, SparseMemory, TrapInfo}};
+    ///
+    /// let mut hart = BasicHart::new(SparseMemory::new(), 0x8000_0000u32);
+    /// let program = [
+    ///     0x00500513, // addi a0,zero,5
+    ///     0x00000593, // addi a1,zero,0
+    ///     0x00a585b3, // add a1,a1,a0
+    ///     0x0000157D, // c.addi a0,-1
+    ///     0xfe051de3, // bnez a0,-6
+    ///     0x800012b7, // lui t0,0x80001
+    ///     0x00b2a223, // sw a1,4(t0)
+    ///     0xfff00313, // addi t1,zero,-1
+    ///     0x00628023, // sb t1,0(t0)
+    ///     0x00028383, // lb t2,0(t0)
+    ///     0x0002ce03, // lbu t3,0(t0)
+    ///     0x0042ae83, // lw t4,4(t0)
+    ///     0x01d3bf33, // sltu t5,t2,t4
+    ///     0x01d3afb3, // slt t6,t2,t4
+    ///     0x008000ef, // jal ra,8
+    ///     0x00100073, // ebreak
+    ///     0x00008067, // jalr zero,0(ra)
+    /// ].map(|i: u32| Instruction::from_parcels([i as u16, (i >> 16) as u16]).0);
+    /// assemble_into(hart.bus(), 0x8000_0000, &program).ok().unwrap();
+    ///
+    /// let trap = loop {
+    ///     if let Err(trap) = hart.step() {
+    ///         break trap;
+    ///     }
+    /// };
+    /// assert!(trap == TrapInfo { cause: Cause::BREAKPOINT, tval: 0x8000_003A });
+    /// assert_eq!(hart.pc(), 0x8000_003A);
+    /// assert_eq!(hart.load_register(Register::X1), 0x8000_003A);
+    /// assert_eq!(hart.load_register(Register::X10), 0);
+    /// assert_eq!(hart.load_register(Register::X11), 15);
+    /// assert_eq!(hart.load_register(Register::X7), -1i32 as u32);
+    /// assert_eq!(hart.load_register(Register::X28), 0xFF);
+    /// assert_eq!(hart.load_register(Register::X29), 15);
+    /// assert_eq!(hart.load_register(Register::X30), 0);
+    /// assert_eq!(hart.load_register(Register::X31), 1);
+    /// assert_eq!(hart.bus().read_u64(0x8000_1000).ok(), Some(0x0000_000F_0000_00FF));
+    /// // The bus can be inspected while the hart is otherwise borrowed.
+    /// let (bus, pc) = (hart.bus_ref(), hart.pc());
+    /// assert_eq!(bus.read_u32(pc).ok(), Some(0x00100073));
+    ///
+    /// // Unknown instructions trap without advancing, reporting their bits.
+    /// hart.set_pc(0x9000_0000);
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::ILLEGAL_INSTRUCTION, tval: 0 }));
+    /// assert_eq!(hart.pc(), 0x9000_0000);
+    /// // c.addi4spn with a zero immediate is reserved, only its parcel is reported.
+    /// let reserved = [Instruction::new(0x0004), Instruction::new(0xFFFF_FFFF)];
+    /// assemble_into(hart.bus(), 0x9000_0000, &reserved).ok().unwrap();
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::ILLEGAL_INSTRUCTION, tval: 0x0004 }));
+    /// hart.set_pc(0x9000_0002);
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::ILLEGAL_INSTRUCTION, tval: 0xFFFF_FFFF }));
+    /// hart.set_pc(0x9000_0001);
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::FETCH_MISALIGN, tval: 0x9000_0001 }));
+    ///
+    /// // ecall reports the current privilege level.
+    /// hart.set_pc(0x9000_0000);
+    /// assemble_into(hart.bus(), 0x9000_0000, &[Instruction::new(0x00000073)]).ok().unwrap();
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::new(11, false), tval: 0 }));
+    /// hart.set_privilege(Privilege::User);
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::new(8, false), tval: 0 }));
+    ///
+    /// // c.addiw a0,1 is not executed as the RV32C c.jal on an RV64 hart.
+    /// let memory = Memory::from_vec(0x1000u64, vec![0x05, 0x25]);
+    /// let mut hart = BasicHart::new(memory, 0x1000);
+    /// assert!(hart.step() == Err(TrapInfo { cause: Cause::ILLEGAL_INSTRUCTION, tval: 0x2505 }));
+    /// assert_eq!((hart.pc(), hart.get_reg(Register::X1), hart.get_reg(Register::X10)), (0x1000, 0, 0));
+    /// # }
+    /// ```
+    fn step(&mut self) -> Result<(), TrapInfo<X>> {
+        let pc = self.pc();
+        let (instruction, _) = self.fetch()?;
+
+        let mut next = instruction.next_pc(pc);
+        let decoded = if instruction.compressed() && X::BITS != 32 {
+            Decoded::Unknown
+        } else {
+            instruction.expand_rv32().map_or(Decoded::Unknown, Instruction::decode)
+        };
+        match decoded {
+            Decoded::Lui { rd, imm } => self.set_reg(rd, immediate(imm)),
+            Decoded::Auipc { rd, imm } => self.set_reg(rd, offset(pc, imm)),
+            Decoded::Jal { rd, imm } => {
+                self.set_reg(rd, next);
+                next = offset(pc, imm);
+            },
+            Decoded::Jalr { rd, rs1, imm } => {
+                let target = offset(self.get_reg(rs1), imm).to_u64() & !1;
+                self.set_reg(rd, next);
+                next = X::from_u64(target);
+            },
+            Decoded::Branch { rs1, rs2, imm, kind } => {
+                let (a, b) = (self.get_reg(rs1), self.get_reg(rs2));
+                let taken = match kind {
+                    BranchKind::Eq => a == b,
+                    BranchKind::Ne => a != b,
+                    BranchKind::Lt => signed(a) < signed(b),
+                    BranchKind::Ge => signed(a) >= signed(b),
+                    BranchKind::Ltu => a < b,
+                    BranchKind::Geu => a >= b
+                };
+                if taken {
+                    next = offset(pc, imm);
+                }
+            },
+            Decoded::Load { rd, rs1, imm, width } => {
+                let address = offset(self.get_reg(rs1), imm);
+                let fault = |cause| TrapInfo { cause, tval: address };
+                let bus = self.bus();
+                let value = match width {
+                    LoadWidth::Byte => bus.read_u8(address).map_err(fault)? as i8 as u64,
+                    LoadWidth::Half => bus.read_u16(address).map_err(fault)? as i16 as u64,
+                    LoadWidth::Word => bus.read_u32(address).map_err(fault)? as i32 as u64,
+                    LoadWidth::ByteUnsigned => bus.read_u8(address).map_err(fault)? as u64,
+                    LoadWidth::HalfUnsigned => bus.read_u16(address).map_err(fault)? as u64
+                };
+                self.set_reg(rd, X::from_u64(value));
+            },
+            Decoded::Store { rs1, rs2, imm, width } => {
+                let address = offset(self.get_reg(rs1), imm);
+                let value = self.get_reg(rs2).to_u64();
+                let fault = |cause| TrapInfo { cause, tval: address };
+                let bus = self.bus();
+                match width {
+                    StoreWidth::Byte => bus.write_u8(address, value as u8).map_err(fault)?,
+                    StoreWidth::Half => bus.write_u16(address, value as u16).map_err(fault)?,
+                    StoreWidth::Word => bus.write_u32(address, value as u32).map_err(fault)?
+                }
+            },
+            Decoded::OpImm { rd, rs1, imm, op } => {
+                let value = alu(op, self.get_reg(rs1), immediate(imm));
+                self.set_reg(rd, value);
+            },
+            Decoded::Op { rd, rs1, rs2, op } => {
+                let value = alu(op, self.get_reg(rs1), self.get_reg(rs2));
+                self.set_reg(rd, value);
+            },
+            // Accesses are performed in program order
+            Decoded::Fence { .. } => (),
+            // Environment call from U-, S- or M-mode
+            Decoded::Ecall => {
+                let cause = Cause::exception(8 + self.privilege() as u64);
+                return Err(TrapInfo { cause, tval: X::from_u64(0) });
+            },
+            Decoded::Ebreak => return Err(TrapInfo { cause: Cause::BREAKPOINT, tval: pc }),
+            Decoded::Unknown => {
+                let tval = X::from_u64(instruction.raw() as u64);
+                return Err(TrapInfo { cause: Cause::ILLEGAL_INSTRUCTION, tval });
+            }
+        }
+        self.set_pc(next);
+        self.on_retire(pc, instruction);
+        Ok(())
+    }
+
+    /// Execute up to `max_instructions` instructions with [`Hart::step`],
+    /// stopping early on a trap.
+    ///
+    /// Returns the number of instructions retired along with the trap, if any.
+    /// As a trapping instruction does not retire it is not counted.
+    /// ```rust
+    /// # #[cfg(feature = "alloc")] {
+    /// use rysk::{Addressable, Cause, Hart, Register, TrapInfo, env::{BasicHart, Memory}};
+    ///
+    /// // loop: c.addi a0,1; c.j loop
+    /// let memory = Memory::from_vec(0x1000u32, vec![0x05, 0x05, 0xFD, 0xBF]);
+    /// let mut hart = BasicHart::new(memory, 0x1000);
+    /// assert!(hart.run(100) == (100, None));
+    /// assert_eq!(hart.get_reg(Register::X10), 50);
+    /// assert!(hart.run(0) == (0, None));
+    ///
+    /// // Running off the end of memory stops early.
+    /// hart.set_pc(0x1000);
+    /// hart.bus().write_u16(0x1002, 0x0505).ok().unwrap();
+    /// assert!(hart.run(10) == (2, Some(TrapInfo { cause: Cause::FETCH_FAULT, tval: 0x1004 })));
+    /// assert_eq!(hart.get_reg(Register::X10), 52);
+    /// # }
+    /// ```
+    fn run(&mut self, max_instructions: usize) -> (usize, Option<TrapInfo<X>>) {
+        for retired in 0..max_instructions {
+            if let Err(trap) = self.step() {
+                return (retired, Some(trap));
+            }
+        }
+        (max_instructions, None)
+    }
+
+    /// Called by [`Hart::step`] after an instruction at `pc` retires, with the
+    /// PC already advanced. Instructions that trap do not retire.
+    ///
+    /// The default does nothing, override it to trace or count instructions.
+    /// ```rust
+    /// # #[cfg(feature = "alloc")] {
+    /// use rysk::{Cause, Hart, Instruction, Register, env::{BasicHart, Memory, Privilege}};
+    ///
+    /// struct Counting { hart: BasicHart<u32, Memory<u32>>, retired: Vec<(u32, Instruction)> }
+    /// impl Hart<u32, Memory<u32>> for Counting {
+    ///     fn load_register(&self, r: Register) -> u32 { self.hart.load_register(r) }
+    ///     fn store_register(&mut self, r: Register, v: u32) { self.hart.store_register(r, v) }
+    ///     fn bus(&mut self) -> &mut Memory<u32> { self.hart.bus() }
+    ///     fn bus_ref(&self) -> &Memory<u32> { self.hart.bus_ref() }
+    ///     fn pc(&self) -> u32 { self.hart.pc() }
+    ///     fn set_pc(&mut self, pc: u32) { self.hart.set_pc(pc) }
+    ///     fn privilege(&self) -> Privilege { self.hart.privilege() }
+    ///     fn set_privilege(&mut self, p: Privilege) { self.hart.set_privilege(p) }
+    ///     fn on_retire(&mut self, pc: u32, instruction: Instruction) { self.retired.push((pc, instruction)) }
+    /// }
+    ///
+    /// // c.li a0,1; addi a0,a0,2; ebreak
+    /// let memory = Memory::from_vec(0x1000, vec![0x05, 0x45, 0x13, 0x05, 0x25, 0x00, 0x73, 0x00, 0x10, 0x00]);
+    /// let mut hart = Counting { hart: BasicHart::new(memory, 0x1000), retired: Vec::new() };
+    /// while hart.step().is_ok() {}
+    /// assert_eq!(hart.retired, [(0x1000, Instruction::new(0x4505)), (0x1002, Instruction::new(0x00250513))]);
+    /// assert_eq!(hart.get_reg(Register::X10), 3);
+    /// # }
+    /// ```
+    #[inline]
+    fn on_retire(&mut self, pc: X, instruction: Instruction) {
+        let _ = (pc, instruction);
+    }
+}
+
+/// Read the little-endian instruction parcel at `address`.
+#[inline]
+fn parcel<X: Xlen, A: Addressable<X>>(bus: &A, address: X) -> Result<u16, Cause<X>> {
+    let mut bytes = [0; 2];
+    bus.read_bytes(address, &mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+/// Sign-extend an XLEN value to 64 bits.
+#[inline]
+fn signed<X: Xlen>(value: X) -> i64 {
+    let shift = 64 - X::BITS as u32;
+    ((value.to_u64() << shift) as i64) >> shift
+}
+/// Sign-extend a decoded immediate to XLEN.
+#[inline]
+fn immediate<X: Xlen>(imm: u32) -> X {
+    X::from_u64(imm as i32 as u64)
+}
+/// Add a decoded immediate to `base`, wrapping at the XLEN boundary.
+#[inline]
+fn offset<X: Xlen>(base: X, imm: u32) -> X {
+    X::from_u64(base.to_u64().wrapping_add(imm as i32 as u64))
+}
+fn alu<X: Xlen>(op: AluOp, a: X, b: X) -> X {
+    let shamt = b.to_u64() & (X::BITS as u64 - 1);
+    let (a64, b64) = (a.to_u64(), b.to_u64());
+    X::from_u64(match op {
+        AluOp::Add => a64.wrapping_add(b64),
+        AluOp::Sub => a64.wrapping_sub(b64),
+        AluOp::Sll => a64 << shamt,
+        AluOp::Slt => (signed(a) < signed(b)) as u64,
+        AluOp::Sltu => (a64 < b64) as u64,
+        AluOp::Xor => a64 ^ b64,
+        AluOp::Srl => a64 >> shamt,
+        AluOp::Sra => (signed(a) >> shamt) as u64,
+        AluOp::Or => a64 | b64,
+        AluOp::And => a64 & b64
+    })
+}
@@ -0,0 +1,122 @@
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::cell::RefCell;
+
+use crate::Xlen;
+
+use super::{wraps, Addressable, Cause};
+
+/// Contiguous RAM of a fixed size mapped at a base address.
+///
+/// Accesses that fall even partly outside the mapped range fault without
+/// touching memory.
+/// ```rust
+/// use rysk::{Addressable, Cause, env::Memory};
+///
+/// let memory = Memory::new(0x8000_0000u32, 16);
+/// assert_eq!(memory.len(), 16);
+/// assert!(memory.write_u8(0x8000_0000, 0x01).is_ok());
+/// assert!(memory.write_u16(0x8000_0002, 0x0302).is_ok());
+/// assert!(memory.write_u32(0x8000_0004, 0x0706_0504).is_ok());
+/// assert!(memory.write_u64(0x8000_0008, 0x0F0E_0D0C_0B0A_0908).is_ok());
+/// assert_eq!(memory.read_u8(0x8000_0000).ok(), Some(0x01));
+/// assert_eq!(memory.read_u16(0x8000_0002).ok(), Some(0x0302));
+/// assert_eq!(memory.read_u32(0x8000_0004).ok(), Some(0x0706_0504));
+/// assert_eq!(memory.read_u64(0x8000_0008).ok(), Some(0x0F0E_0D0C_0B0A_0908));
+/// assert_eq!(memory.read_u32(0x8000_0002).ok(), Some(0x0504_0302));
+///
+/// // Out of range
+/// assert!(memory.read_u8(0x7FFF_FFFF) == Err(Cause::LOAD_FAULT));
+/// assert!(memory.read_u8(0x8000_0010) == Err(Cause::LOAD_FAULT));
+/// assert!(memory.read_u32(0x8000_000E) == Err(Cause::LOAD_FAULT));
+/// assert!(memory.write_u64(0x8000_000A, 0) == Err(Cause::STORE_FAULT));
+/// assert!(memory.write_u8(0, 0) == Err(Cause::STORE_FAULT));
+/// // Faulting stores leave memory untouched.
+/// assert_eq!(memory.read_u64(0x8000_0008).ok(), Some(0x0F0E_0D0C_0B0A_0908));
+///
+/// assert_eq!(memory.into_inner()[..4], [0x01, 0x00, 0x02, 0x03]);
+///
+/// // Memory may end at, but never extend past, the top of the address space.
+/// let memory = Memory::new(0xFFFF_FFF0u32, 0x10);
+/// assert!(memory.read_u64(0xFFFF_FFF8).is_ok());
+/// assert!(memory.read_u64(0xFFFF_FFFC) == Err(Cause::LOAD_FAULT));
+/// assert!(memory.write_u32(0xFFFF_FFFE, 0) == Err(Cause::STORE_FAULT));
+/// ```
+pub struct Memory<X: Xlen> {
+    base: X,
+    bytes: RefCell<Box<[u8]>>
+}
+impl<X: Xlen> Memory<X> {
+    /// Create `len` bytes of zeroed memory starting at `base`.
+    /// # Panics
+    /// Panics if the memory would extend past the top of the address space.
+    /// ```rust,should_panic
+    /// rysk::env::Memory::new(0xFFFF_FFF0u32, 0x20);
+    /// ```
+    pub fn new(base: X, len: usize) -> Self {
+        assert!(!wraps::<X>(base.to_u64(), len), "memory extends past the top of the address space");
+        Self::from_vec(base, vec![0; len])
+    }
+    /// Create memory starting at `base` holding `bytes`.
+    /// # Panics
+    /// Panics if the memory would extend past the top of the address space.
+    pub fn from_vec(base: X, bytes: Vec<u8>) -> Self {
+        assert!(!wraps::<X>(base.to_u64(), bytes.len()), "memory extends past the top of the address space");
+        Self {
+            base,
+            bytes: RefCell::new(bytes.into_boxed_slice())
+        }
+    }
+    /// Return the address of the first byte.
+    #[inline]
+    pub fn base(&self) -> X {
+        self.base
+    }
+    /// Return the size of the memory in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bytes.borrow().len()
+    }
+    /// Returns `true` if the memory has a size of zero bytes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Consume the memory, returning its contents.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.bytes.into_inner().into_vec()
+    }
+
+    /// Return the offset of `len` bytes from `address`, or `None` if any of
+    /// them are out of range or the range wraps past the top of the address
+    /// space.
+    #[inline]
+    fn offset(&self, address: X, len: usize) -> Option<usize> {
+        if wraps::<X>(address.to_u64(), len) {
+            return None;
+        }
+        let offset = address.to_u64().checked_sub(self.base.to_u64())?;
+        let end = offset.checked_add(len as u64)?;
+        (end <= self.len() as u64).then_some(offset as usize)
+    }
+}
+impl<X: Xlen> Addressable<X> for Memory<X> {
+    fn read_u8(&self, address: X) -> Result<u8, Cause<X>> {
+        let offset = self.offset(address, 1).ok_or(Cause::LOAD_FAULT)?;
+        Ok(self.bytes.borrow()[offset])
+    }
+    fn write_u8(&self, address: X, byte: u8) -> Result<(), Cause<X>> {
+        let offset = self.offset(address, 1).ok_or(Cause::STORE_FAULT)?;
+        self.bytes.borrow_mut()[offset] = byte;
+        Ok(())
+    }
+    fn read_bytes(&self, address: X, buf: &mut [u8]) -> Result<(), Cause<X>> {
+        let offset = self.offset(address, buf.len()).ok_or(Cause::LOAD_FAULT)?;
+        buf.copy_from_slice(&self.bytes.borrow()[offset..offset + buf.len()]);
+        Ok(())
+    }
+    fn write_bytes(&self, address: X, data: &[u8]) -> Result<(), Cause<X>> {
+        let offset = self.offset(address, data.len()).ok_or(Cause::STORE_FAULT)?;
+        self.bytes.borrow_mut()[offset..offset + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+}
@@ -1,51 +1,608 @@
-use crate::Xlen;
+use core::marker::PhantomData;
 
+use crate::{AmoOp, Instruction, Xlen};
+
+mod basic;
+mod boot;
+mod csr;
 mod hart;
+#[cfg(feature = "alloc")]
+mod memory;
+mod mstatus;
+mod privilege;
+mod reservation;
+#[cfg(feature = "alloc")]
+mod sparse;
+mod trap;
+mod uart;
+pub use basic::BasicHart;
+pub use boot::{boot_arguments, load_dtb};
+pub use csr::Csr;
 pub use hart::Hart;
+#[cfg(feature = "alloc")]
+pub use memory::Memory;
+pub use mstatus::MStatus;
+pub use privilege::Privilege;
+pub use reservation::{Reservation, ReservationSet, RESERVATION_GRANULE};
+#[cfg(feature = "alloc")]
+pub use sparse::{SparseMemory, PAGE_SIZE};
+pub use trap::TrapInfo;
+pub use uart::Uart;
 
-pub trait Addressable<X: Xlen> {
-    fn read_u8(&self, address: X) -> u8;
-    fn read_u16(&self, address: X) -> u16;
-    fn read_u32(&self, address: X) -> u32;
-    fn read_u64(&self, address: X) -> u64;
+/// The byte order of multi-byte data accesses.
+///
+/// Instructions are always stored little-endian, so this applies to data
+/// accesses only and not to instruction fetch or [`assemble_into`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big
+}
+
+/// How multi-byte accesses to addresses that are not a multiple of their
+/// width are handled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MisalignPolicy {
+    /// Perform the access as if it were aligned.
+    #[default]
+    Allow,
+    /// Raise an address misaligned exception without accessing the bus.
+    Trap
+}
+
+/// Returns `true` if `address` is a multiple of `width` bytes, which must be a
+/// power of two.
+/// ```rust
+/// use rysk::env::is_aligned;
+/// for width in [1, 2, 4, 8] {
+///     assert!(is_aligned(0x8000_0000u32, width));
+///     assert!(is_aligned(0xFFFF_FFFF_FFFF_FFF8u64, width));
+///     assert_eq!(is_aligned(0x8000_0002u32, width), width <= 2);
+///     assert_eq!(is_aligned(0x8000_0004u64, width), width <= 4);
+///     assert_eq!(is_aligned(0x8000_0001u32, width), width == 1);
+/// }
+/// ```
+#[inline]
+pub fn is_aligned<X: Xlen>(address: X, width: usize) -> bool {
+    address.to_u64() & (width as u64 - 1) == 0
+}
 
+macro_rules! accesses {
+    ($($(#[$doc:meta])* $read:ident, $write:ident: $ty:ty;)*) => {$(
+        $(#[$doc])*
+        fn $read(&self, address: X) -> Result<$ty, Cause<X>> {
+            let mut bytes = [0; core::mem::size_of::<$ty>()];
+            if self.misalign_policy() == MisalignPolicy::Trap && !is_aligned(address, bytes.len()) {
+                return Err(Cause::LOAD_MISALIGN);
+            }
+            self.read_bytes(address, &mut bytes)?;
+            Ok(match self.endianness() {
+                Endianness::Little => <$ty>::from_le_bytes(bytes),
+                Endianness::Big => <$ty>::from_be_bytes(bytes)
+            })
+        }
+        $(#[$doc])*
+        fn $write(&self, address: X, value: $ty) -> Result<(), Cause<X>> {
+            let bytes = match self.endianness() {
+                Endianness::Little => value.to_le_bytes(),
+                Endianness::Big => value.to_be_bytes()
+            };
+            if self.misalign_policy() == MisalignPolicy::Trap && !is_aligned(address, bytes.len()) {
+                return Err(Cause::STORE_MISALIGN);
+            }
+            self.write_bytes(address, &bytes)
+        }
+    )*};
+}
+
+macro_rules! amos {
+    ($(
+        $(#[$doc:meta])*
+        $amo:ident, $cas:ident: $ty:ty, $signed:ty, $read:ident, $write:ident;
+    )*) => {$(
+        $(#[$doc])*
+        fn $amo(&self, address: X, op: AmoOp, value: $ty) -> Result<$ty, Cause<X>> {
+            if !is_aligned(address, core::mem::size_of::<$ty>()) {
+                return Err(Cause::STORE_MISALIGN);
+            }
+            let old = self.$read(address).map_err(amo_fault)?;
+            let new = match op {
+                AmoOp::Swap => value,
+                AmoOp::Add => old.wrapping_add(value),
+                AmoOp::And => old & value,
+                AmoOp::Or => old | value,
+                AmoOp::Xor => old ^ value,
+                AmoOp::Min => (old as $signed).min(value as $signed) as $ty,
+                AmoOp::Max => (old as $signed).max(value as $signed) as $ty,
+                AmoOp::Minu => old.min(value),
+                AmoOp::Maxu => old.max(value)
+            };
+            self.$write(address, new)?;
+            Ok(old)
+        }
+        /// Atomically replace the value at `address` with `new` if it equals
+        /// `expected`, returning the previous value.
+        ///
+        /// The default has the same atomicity, alignment and fault behaviour
+        /// as the AMOs.
+        fn $cas(&self, address: X, expected: $ty, new: $ty) -> Result<$ty, Cause<X>> {
+            if !is_aligned(address, core::mem::size_of::<$ty>()) {
+                return Err(Cause::STORE_MISALIGN);
+            }
+            let old = self.$read(address).map_err(amo_fault)?;
+            if old == expected {
+                self.$write(address, new)?;
+            }
+            Ok(old)
+        }
+    )*};
+}
+
+/// A bus or device that can be accessed by address.
+///
+/// Only the byte accesses are required. The multi-byte accesses default to
+/// composing bytes in the order given by [`Addressable::endianness`] and
+/// checking alignment as given by [`Addressable::misalign_policy`], while
+/// implementations overriding them for speed are responsible for honouring
+/// both themselves.
+/// ```rust
+/// use rysk::{Addressable, Cause, env::Endianness};
+///
+/// struct Rom(Endianness, [u8; 4]);
+/// impl Addressable<u32> for Rom {
+///     fn read_u8(&self, address: u32) -> Result<u8, Cause<u32>> {
+///         self.1.get(address as usize).copied().ok_or(Cause::LOAD_FAULT)
+///     }
+///     fn write_u8(&self, _: u32, _: u8) -> Result<(), Cause<u32>> {
+///         Err(Cause::STORE_FAULT)
+///     }
+///     fn endianness(&self) -> Endianness {
+///         self.0
+///     }
+/// }
+///
+/// let bytes = [0x12, 0x34, 0x56, 0x78];
+/// assert_eq!(Rom(Endianness::Little, bytes).read_u32(0).ok(), Some(0x7856_3412));
+/// assert_eq!(Rom(Endianness::Big, bytes).read_u32(0).ok(), Some(0x1234_5678));
+/// assert_eq!(Rom(Endianness::Little, bytes).read_u16(2).ok(), Some(0x7856));
+/// assert_eq!(Rom(Endianness::Big, bytes).read_u16(2).ok(), Some(0x5678));
+/// assert!(Rom(Endianness::Big, bytes).read_u16(3) == Err(Cause::LOAD_FAULT));
+/// ```
+pub trait Addressable<X: Xlen> {
+    fn read_u8(&self, address: X) -> Result<u8, Cause<X>>;
     fn write_u8(&self, address: X, byte: u8) -> Result<(), Cause<X>>;
-    fn write_u16(&self, address: X, halfword: u16) -> Result<(), Cause<X>>;
-    fn write_u32(&self, address: X, word: u32) -> Result<(), Cause<X>>;
-    fn write_u64(&self, address: X, doubleword: u64) -> Result<(), Cause<X>>;
+
+    /// Return the byte order of multi-byte accesses, little-endian unless
+    /// overridden.
+    #[inline]
+    fn endianness(&self) -> Endianness {
+        Endianness::Little
+    }
+    /// Return how misaligned multi-byte accesses are handled, allowing them
+    /// unless overridden.
+    /// ```rust
+    /// use rysk::{Addressable, Cause, env::MisalignPolicy};
+    ///
+    /// struct Ram;
+    /// impl Addressable<u32> for Ram {
+    ///     fn read_u8(&self, _: u32) -> Result<u8, Cause<u32>> { Ok(0) }
+    ///     fn write_u8(&self, _: u32, _: u8) -> Result<(), Cause<u32>> { Ok(()) }
+    ///     fn misalign_policy(&self) -> MisalignPolicy { MisalignPolicy::Trap }
+    /// }
+    ///
+    /// assert!(Ram.read_u32(0x1004).is_ok());
+    /// assert!(Ram.read_u32(0x1002) == Err(Cause::LOAD_MISALIGN));
+    /// assert!(Ram.write_u16(0x1002, 0).is_ok());
+    /// assert!(Ram.write_u64(0x1004, 0) == Err(Cause::STORE_MISALIGN));
+    /// // Byte accesses are never misaligned.
+    /// assert!(Ram.write_bytes(0x1001, &[0; 4]).is_ok());
+    /// ```
+    #[inline]
+    fn misalign_policy(&self) -> MisalignPolicy {
+        MisalignPolicy::Allow
+    }
+
+    accesses! {
+        /// Access a halfword in the bus byte order.
+        read_u16, write_u16: u16;
+        /// Access a word in the bus byte order.
+        read_u32, write_u32: u32;
+        /// Access a doubleword in the bus byte order.
+        read_u64, write_u64: u64;
+    }
+
+    amos! {
+        /// Atomically apply `op` to the word at `address` and `value`,
+        /// returning the previous word.
+        ///
+        /// The default reads then writes the word, which is only atomic for
+        /// single-threaded emulators. AMOs must be naturally aligned whatever
+        /// the [misalignment policy](Addressable::misalign_policy), and faults
+        /// are raised as store/AMO exceptions.
+        /// ```rust
+        /// # #[cfg(feature = "alloc")] {
+        /// use rysk::{Addressable, AmoOp, env::SparseMemory};
+        /// let amo = |op, old: u32, value: u32| {
+        ///     let memory = SparseMemory::new();
+        ///     memory.write_u32(0x8000_0000u32, old).ok().unwrap();
+        ///     let returned = memory.amo_u32(0x8000_0000, op, value).ok().unwrap();
+        ///     (returned, memory.read_u32(0x8000_0000).ok().unwrap())
+        /// };
+        /// assert_eq!(amo(AmoOp::Swap, 1, 2), (1, 2));
+        /// assert_eq!(amo(AmoOp::Add, u32::MAX, 2), (u32::MAX, 1));
+        /// assert_eq!(amo(AmoOp::And, 0b1100, 0b1010), (0b1100, 0b1000));
+        /// assert_eq!(amo(AmoOp::Or, 0b1100, 0b1010), (0b1100, 0b1110));
+        /// assert_eq!(amo(AmoOp::Xor, 0b1100, 0b1010), (0b1100, 0b0110));
+        /// assert_eq!(amo(AmoOp::Min, -1i32 as u32, 1), (-1i32 as u32, -1i32 as u32));
+        /// assert_eq!(amo(AmoOp::Max, -1i32 as u32, 1), (-1i32 as u32, 1));
+        /// assert_eq!(amo(AmoOp::Minu, -1i32 as u32, 1), (-1i32 as u32, 1));
+        /// assert_eq!(amo(AmoOp::Maxu, -1i32 as u32, 1), (-1i32 as u32, -1i32 as u32));
+        /// # }
+        /// ```
+        amo_u32, compare_and_swap_u32: u32, i32, read_u32, write_u32;
+        /// Atomically apply `op` to the doubleword at `address` and `value`,
+        /// returning the previous doubleword.
+        /// ```rust
+        /// # #[cfg(feature = "alloc")] {
+        /// use rysk::{Addressable, AmoOp, Cause, env::SparseMemory};
+        /// let memory = SparseMemory::new();
+        /// memory.write_u64(0x8000_0000u64, 0x1_0000_0000).ok().unwrap();
+        /// assert_eq!(memory.amo_u64(0x8000_0000, AmoOp::Max, -1i64 as u64).ok(), Some(0x1_0000_0000));
+        /// assert_eq!(memory.amo_u64(0x8000_0000, AmoOp::Maxu, -1i64 as u64).ok(), Some(0x1_0000_0000));
+        /// assert_eq!(memory.amo_u64(0x8000_0000, AmoOp::Add, 2).ok(), Some(u64::MAX));
+        /// assert_eq!(memory.read_u64(0x8000_0000).ok(), Some(1));
+        /// assert!(memory.amo_u64(0x8000_0004, AmoOp::Add, 2) == Err(Cause::STORE_MISALIGN));
+        ///
+        /// assert_eq!(memory.compare_and_swap_u64(0x8000_0000, 2, 3).ok(), Some(1));
+        /// assert_eq!(memory.compare_and_swap_u64(0x8000_0000, 1, 3).ok(), Some(1));
+        /// assert_eq!(memory.read_u64(0x8000_0000).ok(), Some(3));
+        /// # }
+        /// ```
+        amo_u64, compare_and_swap_u64: u64, i64, read_u64, write_u64;
+    }
+
+    /// Fill `buf` with the bytes starting at `address`.
+    ///
+    /// Raises a load access fault without reading if the range would wrap
+    /// past the top of the address space.
+    /// ```rust
+    /// # #[cfg(feature = "alloc")] {
+    /// use rysk::{Addressable, Cause, env::SparseMemory};
+    /// let memory = SparseMemory::new();
+    /// assert!(memory.write_bytes(0x8000_0FFEu32, &[1, 2, 3, 4]).is_ok());
+    /// let mut buf = [0; 4];
+    /// assert!(memory.read_bytes(0x8000_0FFE, &mut buf).is_ok());
+    /// assert_eq!(buf, [1, 2, 3, 4]);
+    /// assert_eq!(memory.read_u32(0x8000_0FFE).ok(), Some(0x0403_0201));
+    ///
+    /// // Accesses may end at, but not cross, the top of the address space.
+    /// assert!(memory.write_bytes(0xFFFF_FFFC, &[0; 4]).is_ok());
+    /// assert!(memory.write_bytes(0xFFFF_FFFD, &[0; 4]) == Err(Cause::STORE_FAULT));
+    /// assert!(memory.read_bytes(0xFFFF_FFFD, &mut buf) == Err(Cause::LOAD_FAULT));
+    /// # }
+    /// ```
+    fn read_bytes(&self, address: X, buf: &mut [u8]) -> Result<(), Cause<X>> {
+        let base = address.to_u64();
+        if wraps::<X>(base, buf.len()) {
+            return Err(Cause::LOAD_FAULT);
+        }
+        for (i, byte) in buf.iter_mut().enumerate() {
+            *byte = self.read_u8(X::from_u64(base + i as u64))?;
+        }
+        Ok(())
+    }
+    /// Write `data` to consecutive bytes starting at `address`.
+    ///
+    /// Raises a store access fault without writing if the range would wrap
+    /// past the top of the address space.
+    fn write_bytes(&self, address: X, data: &[u8]) -> Result<(), Cause<X>> {
+        let base = address.to_u64();
+        if wraps::<X>(base, data.len()) {
+            return Err(Cause::STORE_FAULT);
+        }
+        for (i, &byte) in data.iter().enumerate() {
+            self.write_u8(X::from_u64(base + i as u64), byte)?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub struct Cause<X: Xlen>(X);
-impl Cause<u32> {
-    const INTERRUPT_BIT: u32 = 1 << (u32::BITS - 1);
-    /// Returns `true` if the [`Cause`] is an interrupt.
-    pub fn interrupt(self) -> bool {
-        self.0 & Self::INTERRUPT_BIT != 0
+/// Raise a fault from the read half of an AMO as a store/AMO exception.
+#[inline]
+fn amo_fault<X: Xlen>(cause: Cause<X>) -> Cause<X> {
+    if cause == Cause::LOAD_FAULT {
+        Cause::STORE_FAULT
+    } else if cause == Cause::LOAD_MISALIGN {
+        Cause::STORE_MISALIGN
+    } else {
+        cause
     }
+}
 
-    pub const FETCH_MISALIGN: Self = Self(0);
-    pub const FETCH_FAULT: Self = Self(1);
-    pub const ILLEGAL_INSTRUCTION: Self = Self(2);
-    pub const BREAKPOINT: Self = Self(3);
-    pub const LOAD_MISALIGN: Self = Self(4);
-    pub const LOAD_FAULT: Self = Self(5);
-    pub const STORE_MISALIGN: Self = Self(6);
-    pub const STORE_FAULT: Self = Self(7);
+/// Returns `true` if `len` bytes from `base` would wrap past the top of the
+/// XLEN address space.
+#[inline]
+fn wraps<X: Xlen>(base: u64, len: usize) -> bool {
+    let top = u64::MAX >> (64 - X::BITS);
+    len != 0 && len as u64 - 1 > top - base
 }
-impl Cause<u64> {
-    const INTERRUPT_BIT: u64 = 1 << (u64::BITS - 1);
+
+/// Write `instructions` to consecutive addresses starting at `base`, returning
+/// the address following the last instruction.
+///
+/// Compressed instructions occupy a single parcel and standard instructions
+/// two. Each parcel is written as a little-endian halfword, whatever the
+/// [bus endianness](Addressable::endianness), so standard instructions need
+/// only be 16-bit aligned.
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use rysk::{Addressable, Cause, Instruction, Register, env::{assemble_into, Endianness, SparseMemory}};
+/// let memory = SparseMemory::new();
+/// let end = assemble_into(&memory, 0x8000_0000u32, &[
+///     // c.addi a0,-1
+///     Instruction::encode_ci(0b01, 0b000, Register::X10, -1).unwrap(),
+///     // jal x7,0xDEAD4
+///     Instruction::new(0x2d5de3ef),
+/// ]);
+/// assert_eq!(end.ok(), Some(0x8000_0006));
+/// assert_eq!(memory.read_u16(0x8000_0000).ok(), Some(0x157D));
+/// assert_eq!(memory.read_u16(0x8000_0002).ok(), Some(0xE3EF));
+/// assert_eq!(memory.read_u16(0x8000_0004).ok(), Some(0x2D5D));
+///
+/// // A big-endian data bus still holds little-endian code.
+/// struct Big(SparseMemory<u32>);
+/// impl Addressable<u32> for Big {
+///     fn read_u8(&self, address: u32) -> Result<u8, Cause<u32>> { self.0.read_u8(address) }
+///     fn write_u8(&self, address: u32, byte: u8) -> Result<(), Cause<u32>> { self.0.write_u8(address, byte) }
+///     fn endianness(&self) -> Endianness { Endianness::Big }
+/// }
+/// let big = Big(SparseMemory::new());
+/// assemble_into(&big, 0, &[Instruction::new(0x2d5de3ef)]).ok().unwrap();
+/// let mut bytes = [0; 4];
+/// big.read_bytes(0, &mut bytes).ok().unwrap();
+/// assert_eq!(bytes, [0xEF, 0xE3, 0x5D, 0x2D]);
+/// # }
+/// ```
+pub fn assemble_into<X: Xlen, A: Addressable<X>>(memory: &A, base: X, instructions: &[Instruction]) -> Result<X, Cause<X>> {
+    let mut address = base.to_u64();
+    for instruction in instructions {
+        let parcels: &[u16] = if instruction.compressed() {
+            &[instruction.0 as u16]
+        } else {
+            &[instruction.0 as u16, (instruction.0 >> 16) as u16]
+        };
+        for &parcel in parcels {
+            memory.write_bytes(X::from_u64(address), &parcel.to_le_bytes())?;
+            address = address.wrapping_add(2);
+        }
+    }
+    Ok(X::from_u64(address))
+}
+
+/// The cause of a trap, as written to `mcause`.
+///
+/// The raw value is held zero-extended so the constants can be shared by every
+/// XLEN, with the interrupt bit in the most significant bit of the XLEN.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Cause<X: Xlen>(u64, PhantomData<X>);
+
+/// Map a trap to the GDB signal numbering used by the remote protocol.
+///
+/// Misaligned accesses map to `SIGBUS`, access and page faults to `SIGSEGV`,
+/// illegal instructions to `SIGILL` and everything else, including
+/// breakpoints, environment calls and interrupts, to `SIGTRAP`.
+fn gdb_signal(interrupt: bool, code: u64) -> u8 {
+    const SIGILL: u8 = 4;
+    const SIGTRAP: u8 = 5;
+    const SIGBUS: u8 = 10;
+    const SIGSEGV: u8 = 11;
+    if interrupt {
+        return SIGTRAP;
+    }
+    match code {
+        // Instruction, load and store address misaligned
+        0 | 4 | 6 => SIGBUS,
+        // Instruction, load and store access and page faults
+        1 | 5 | 7 | 12 | 13 | 15 => SIGSEGV,
+        2 => SIGILL,
+        _ => SIGTRAP
+    }
+}
+/// Return the name of the associated constant for a cause, or `None` for
+/// non-standard causes.
+pub(crate) fn cause_name(interrupt: bool, code: u64) -> Option<&'static str> {
+    Some(match (interrupt, code) {
+        (false, 0) => "FETCH_MISALIGN",
+        (false, 1) => "FETCH_FAULT",
+        (false, 2) => "ILLEGAL_INSTRUCTION",
+        (false, 3) => "BREAKPOINT",
+        (false, 4) => "LOAD_MISALIGN",
+        (false, 5) => "LOAD_FAULT",
+        (false, 6) => "STORE_MISALIGN",
+        (false, 7) => "STORE_FAULT",
+        (true, 1) => "SUPERVISOR_SOFTWARE",
+        (true, 3) => "MACHINE_SOFTWARE",
+        (true, 5) => "SUPERVISOR_TIMER",
+        (true, 7) => "MACHINE_TIMER",
+        (true, 9) => "SUPERVISOR_EXTERNAL",
+        (true, 11) => "MACHINE_EXTERNAL",
+        _ => return None
+    })
+}
+/// Format a cause as the name of its associated constant, falling back to the
+/// raw code for non-standard causes.
+fn debug_cause(f: &mut core::fmt::Formatter<'_>, interrupt: bool, code: u64) -> core::fmt::Result {
+    match cause_name(interrupt, code) {
+        Some(name) => write!(f, "Cause::{name}"),
+        None => write!(f, "Cause(code={code}, interrupt={interrupt})")
+    }
+}
+/// ```rust
+/// use rysk::Cause;
+/// assert_eq!(format!("{:?}", Cause::<u32>::ILLEGAL_INSTRUCTION), "Cause::ILLEGAL_INSTRUCTION");
+/// assert_eq!(format!("{:?}", Cause::<u64>::LOAD_FAULT), "Cause::LOAD_FAULT");
+/// ```
+impl<X: Xlen> core::fmt::Debug for Cause<X> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        debug_cause(f, self.interrupt(), self.0 & !Self::INTERRUPT_BIT)
+    }
+}
+/// Formats a description of the cause as given in the privileged
+/// specification.
+/// ```rust
+/// use rysk::Cause;
+/// assert_eq!(Cause::<u32>::ILLEGAL_INSTRUCTION.to_string(), "illegal instruction");
+/// assert_eq!(Cause::<u64>::STORE_FAULT.to_string(), "store/AMO access fault");
+/// assert_eq!(Cause::<u32>::MACHINE_TIMER.to_string(), "machine timer interrupt");
+/// assert_eq!(Cause::<u32>::new(8, false).to_string(), "environment call from U-mode");
+/// assert_eq!(Cause::<u64>::new(24, false).to_string(), "exception 24");
+/// assert_eq!(Cause::<u64>::new(16, true).to_string(), "interrupt 16");
+/// ```
+impl<X: Xlen> core::fmt::Display for Cause<X> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let code = self.code().to_u64();
+        let description = match (self.interrupt(), code) {
+            (false, 0) => "instruction address misaligned",
+            (false, 1) => "instruction access fault",
+            (false, 2) => "illegal instruction",
+            (false, 3) => "breakpoint",
+            (false, 4) => "load address misaligned",
+            (false, 5) => "load access fault",
+            (false, 6) => "store/AMO address misaligned",
+            (false, 7) => "store/AMO access fault",
+            (false, 8) => "environment call from U-mode",
+            (false, 9) => "environment call from S-mode",
+            (false, 11) => "environment call from M-mode",
+            (false, 12) => "instruction page fault",
+            (false, 13) => "load page fault",
+            (false, 15) => "store/AMO page fault",
+            (true, 1) => "supervisor software interrupt",
+            (true, 3) => "machine software interrupt",
+            (true, 5) => "supervisor timer interrupt",
+            (true, 7) => "machine timer interrupt",
+            (true, 9) => "supervisor external interrupt",
+            (true, 11) => "machine external interrupt",
+            (false, _) => return write!(f, "exception {code}"),
+            (true, _) => return write!(f, "interrupt {code}")
+        };
+        f.write_str(description)
+    }
+}
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use rysk::Cause;
+/// use std::error::Error;
+///
+/// fn fetch() -> Result<(), Box<dyn Error>> {
+///     Err(Cause::<u32>::FETCH_FAULT)?
+/// }
+/// assert_eq!(fetch().unwrap_err().to_string(), "instruction access fault");
+///
+/// for cause in [
+///     Cause::<u32>::FETCH_MISALIGN,
+///     Cause::FETCH_FAULT,
+///     Cause::ILLEGAL_INSTRUCTION,
+///     Cause::BREAKPOINT,
+///     Cause::LOAD_MISALIGN,
+///     Cause::LOAD_FAULT,
+///     Cause::STORE_MISALIGN,
+///     Cause::STORE_FAULT,
+///     Cause::SUPERVISOR_SOFTWARE,
+///     Cause::MACHINE_SOFTWARE,
+///     Cause::SUPERVISOR_TIMER,
+///     Cause::MACHINE_TIMER,
+///     Cause::SUPERVISOR_EXTERNAL,
+///     Cause::MACHINE_EXTERNAL,
+/// ] {
+///     let error: &dyn Error = &cause;
+///     assert!(!error.to_string().is_empty());
+///     assert!(error.source().is_none());
+/// }
+/// # }
+/// ```
+#[cfg(feature = "std")]
+impl<X: Xlen> std::error::Error for Cause<X> {}
+impl<X: Xlen> Cause<X> {
+    const INTERRUPT_BIT: u64 = 1 << (X::BITS - 1);
+
+    #[inline]
+    const fn raw(value: u64) -> Self {
+        Self(value, PhantomData)
+    }
+    /// Create an exception cause from its code.
+    #[inline]
+    pub(crate) fn exception(code: u64) -> Self {
+        Self::raw(code & !Self::INTERRUPT_BIT)
+    }
+    /// Create a cause from its code, setting the most significant bit if it
+    /// is an `interrupt` and clearing it otherwise.
+    /// ```rust
+    /// use rysk::Cause;
+    /// assert!(Cause::<u32>::new(2, false) == Cause::<u32>::ILLEGAL_INSTRUCTION);
+    /// assert_eq!(Cause::<u32>::new(11, true).code(), 11);
+    /// assert!(Cause::<u64>::new(11, true).interrupt());
+    /// assert_eq!(format!("{:?}", Cause::<u64>::new(24, false)), "Cause(code=24, interrupt=false)");
+    /// ```
+    #[inline]
+    pub fn new(code: X, interrupt: bool) -> Self {
+        let code = code.to_u64() & !Self::INTERRUPT_BIT;
+        if interrupt {
+            Self::raw(code | Self::INTERRUPT_BIT)
+        } else {
+            Self::raw(code)
+        }
+    }
+    /// Returns the exception or interrupt code without the interrupt bit.
+    #[inline]
+    pub fn code(self) -> X {
+        X::from_u64(self.0 & !Self::INTERRUPT_BIT)
+    }
     /// Returns `true` if the [`Cause`] is an interrupt.
     pub fn interrupt(self) -> bool {
         self.0 & Self::INTERRUPT_BIT != 0
     }
+    /// Returns the GDB signal number conventionally reported for the
+    /// [`Cause`] in a remote protocol stop reply.
+    /// ```rust
+    /// use rysk::Cause;
+    /// assert_eq!(Cause::<u32>::BREAKPOINT.to_gdb_signal(), 5);
+    /// assert_eq!(Cause::<u32>::ILLEGAL_INSTRUCTION.to_gdb_signal(), 4);
+    /// assert_eq!(Cause::<u64>::LOAD_FAULT.to_gdb_signal(), 11);
+    /// assert_eq!(Cause::<u64>::STORE_MISALIGN.to_gdb_signal(), 10);
+    /// ```
+    pub fn to_gdb_signal(self) -> u8 {
+        gdb_signal(self.interrupt(), self.0 & !Self::INTERRUPT_BIT)
+    }
+
+    pub const FETCH_MISALIGN: Self = Self::raw(0);
+    pub const FETCH_FAULT: Self = Self::raw(1);
+    pub const ILLEGAL_INSTRUCTION: Self = Self::raw(2);
+    pub const BREAKPOINT: Self = Self::raw(3);
+    pub const LOAD_MISALIGN: Self = Self::raw(4);
+    pub const LOAD_FAULT: Self = Self::raw(5);
+    pub const STORE_MISALIGN: Self = Self::raw(6);
+    pub const STORE_FAULT: Self = Self::raw(7);
 
-    pub const FETCH_MISALIGN: Self = Self(0);
-    pub const FETCH_FAULT: Self = Self(1);
-    pub const ILLEGAL_INSTRUCTION: Self = Self(2);
-    pub const BREAKPOINT: Self = Self(3);
-    pub const LOAD_MISALIGN: Self = Self(4);
-    pub const LOAD_FAULT: Self = Self(5);
-    pub const STORE_MISALIGN: Self = Self(6);
-    pub const STORE_FAULT: Self = Self(7);
+    /// Supervisor software interrupt. Interrupt causes have the interrupt bit set.
+    /// ```rust
+    /// use rysk::Cause;
+    /// for cause in [
+    ///     Cause::<u32>::SUPERVISOR_SOFTWARE, Cause::<u32>::MACHINE_SOFTWARE,
+    ///     Cause::<u32>::SUPERVISOR_TIMER, Cause::<u32>::MACHINE_TIMER,
+    ///     Cause::<u32>::SUPERVISOR_EXTERNAL, Cause::<u32>::MACHINE_EXTERNAL
+    /// ] {
+    ///     assert!(cause.interrupt());
+    /// }
+    /// assert_eq!(Cause::<u32>::MACHINE_TIMER.code(), 7);
+    /// assert!(!Cause::<u32>::STORE_FAULT.interrupt());
+    /// assert!(Cause::<u64>::MACHINE_EXTERNAL.interrupt());
+    /// assert_eq!(format!("{:?}", Cause::<u64>::MACHINE_EXTERNAL), "Cause::MACHINE_EXTERNAL");
+    /// ```
+    pub const SUPERVISOR_SOFTWARE: Self = Self::raw(1 | Self::INTERRUPT_BIT);
+    pub const MACHINE_SOFTWARE: Self = Self::raw(3 | Self::INTERRUPT_BIT);
+    pub const SUPERVISOR_TIMER: Self = Self::raw(5 | Self::INTERRUPT_BIT);
+    pub const MACHINE_TIMER: Self = Self::raw(7 | Self::INTERRUPT_BIT);
+    pub const SUPERVISOR_EXTERNAL: Self = Self::raw(9 | Self::INTERRUPT_BIT);
+    pub const MACHINE_EXTERNAL: Self = Self::raw(11 | Self::INTERRUPT_BIT);
 }
@@ -0,0 +1,204 @@
+use crate::Xlen;
+
+use super::Privilege;
+
+/// The machine status register, `mstatus`.
+///
+/// Fields shared by RV32 and RV64 sit at the same positions. On RV32 the
+/// `SXL`/`UXL`/`SBE`/`MBE` fields live in `mstatush` and are not represented
+/// here, while `SD` always occupies the most significant bit.
+/// ```rust
+/// use rysk::env::{MStatus, Privilege};
+/// let mut mstatus = MStatus::new(0u64);
+/// mstatus.set_mie(true);
+/// mstatus.set_mpp(Privilege::Machine);
+/// mstatus.set_sxl(2);
+/// assert!(mstatus.mie());
+/// assert_eq!(mstatus.mpp(), Some(Privilege::Machine));
+/// assert_eq!(mstatus.sxl(), Some(2));
+/// assert_eq!(mstatus.bits(), 0x0000_0008_0000_1808);
+///
+/// // Dirty floating-point state is summarised in SD.
+/// let mut mstatus = MStatus::new(0u32);
+/// mstatus.set_fs(0b11);
+/// assert!(mstatus.sd());
+/// assert_eq!(mstatus.bits(), 0x8000_6000);
+/// assert_eq!(mstatus.sxl(), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct MStatus<X: Xlen>(X);
+
+macro_rules! flags {
+    ($($(#[$doc:meta])* $get:ident, $set:ident = $bit:literal;)*) => {$(
+        $(#[$doc])*
+        #[inline]
+        pub fn $get(self) -> bool {
+            self.field($bit, 1) != 0
+        }
+        $(#[$doc])*
+        #[inline]
+        pub fn $set(&mut self, value: bool) {
+            self.set_field($bit, 1, value as u64)
+        }
+    )*};
+}
+
+impl<X: Xlen> MStatus<X> {
+    /// Wrap a raw register value, recomputing `SD` from the `FS`, `VS` and
+    /// `XS` dirty states.
+    /// ```rust
+    /// use rysk::env::MStatus;
+    /// assert_eq!(MStatus::new(0x8000_0000u32).bits(), 0);
+    /// assert_eq!(MStatus::new(0x0000_0600u32).bits(), 0x8000_0600);
+    /// assert!(MStatus::new(0x0001_8000u64).sd());
+    /// ```
+    #[inline]
+    pub fn new(value: X) -> Self {
+        let mut mstatus = Self(value);
+        mstatus.update_sd();
+        mstatus
+    }
+    /// Return the raw register value.
+    #[inline]
+    pub fn bits(self) -> X {
+        self.0
+    }
+
+    #[inline]
+    fn field(self, lsb: u32, width: u32) -> u64 {
+        (self.0.to_u64() >> lsb) & ((1 << width) - 1)
+    }
+    #[inline]
+    fn set_field(&mut self, lsb: u32, width: u32, value: u64) {
+        let mask = ((1 << width) - 1) << lsb;
+        self.0 = X::from_u64((self.0.to_u64() & !mask) | ((value << lsb) & mask));
+    }
+    /// Recompute `SD` from the `FS`, `VS` and `XS` dirty states.
+    fn update_sd(&mut self) {
+        let dirty = self.fs() == 0b11 || self.vs() == 0b11 || self.xs() == 0b11;
+        self.set_field(X::BITS as u32 - 1, 1, dirty as u64)
+    }
+
+    flags! {
+        /// Supervisor interrupt enable.
+        sie, set_sie = 1;
+        /// Machine interrupt enable.
+        mie, set_mie = 3;
+        /// Supervisor previous interrupt enable.
+        spie, set_spie = 5;
+        /// User-mode big-endian data accesses.
+        ube, set_ube = 6;
+        /// Machine previous interrupt enable.
+        mpie, set_mpie = 7;
+        /// Supervisor previous privilege, set if it was supervisor mode.
+        spp, set_spp = 8;
+        /// Modify privilege of loads and stores to that of `MPP`.
+        mprv, set_mprv = 17;
+        /// Permit supervisor access to user memory.
+        sum, set_sum = 18;
+        /// Make executable pages readable.
+        mxr, set_mxr = 19;
+        /// Trap virtual memory management from supervisor mode.
+        tvm, set_tvm = 20;
+        /// Timeout wait, trapping `wfi` below machine mode.
+        tw, set_tw = 21;
+        /// Trap `sret` from supervisor mode.
+        tsr, set_tsr = 22;
+    }
+
+    /// Machine previous privilege level, or [`None`] for the reserved level.
+    /// ```rust
+    /// use rysk::env::{MStatus, Privilege};
+    /// assert_eq!(MStatus::new(0x0000_0800u32).mpp(), Some(Privilege::Supervisor));
+    /// assert_eq!(MStatus::new(0x0000_1000u32).mpp(), None);
+    /// ```
+    #[inline]
+    pub fn mpp(self) -> Option<Privilege> {
+        Privilege::try_from(self.field(11, 2) as u8).ok()
+    }
+    /// Machine previous privilege level.
+    #[inline]
+    pub fn set_mpp(&mut self, privilege: Privilege) {
+        self.set_field(11, 2, privilege as u64)
+    }
+    /// Vector extension state.
+    #[inline]
+    pub fn vs(self) -> u8 {
+        self.field(9, 2) as u8
+    }
+    /// Vector extension state, updating `SD`.
+    pub fn set_vs(&mut self, state: u8) {
+        self.set_field(9, 2, state as u64);
+        self.update_sd()
+    }
+    /// Floating-point unit state.
+    #[inline]
+    pub fn fs(self) -> u8 {
+        self.field(13, 2) as u8
+    }
+    /// Floating-point unit state, updating `SD`.
+    pub fn set_fs(&mut self, state: u8) {
+        self.set_field(13, 2, state as u64);
+        self.update_sd()
+    }
+    /// Additional user-mode extension state.
+    #[inline]
+    pub fn xs(self) -> u8 {
+        self.field(15, 2) as u8
+    }
+    /// Additional user-mode extension state, updating `SD`.
+    pub fn set_xs(&mut self, state: u8) {
+        self.set_field(15, 2, state as u64);
+        self.update_sd()
+    }
+    /// State dirty summary, set if any of `FS`, `VS` or `XS` are dirty.
+    #[inline]
+    pub fn sd(self) -> bool {
+        self.field(X::BITS as u32 - 1, 1) != 0
+    }
+
+    /// User-mode XLEN, or [`None`] on RV32.
+    #[inline]
+    pub fn uxl(self) -> Option<u8> {
+        (X::BITS == 64).then(|| self.field(32, 2) as u8)
+    }
+    /// User-mode XLEN. Ignored on RV32.
+    pub fn set_uxl(&mut self, xl: u8) {
+        if X::BITS == 64 {
+            self.set_field(32, 2, xl as u64)
+        }
+    }
+    /// Supervisor-mode XLEN, or [`None`] on RV32.
+    #[inline]
+    pub fn sxl(self) -> Option<u8> {
+        (X::BITS == 64).then(|| self.field(34, 2) as u8)
+    }
+    /// Supervisor-mode XLEN. Ignored on RV32.
+    pub fn set_sxl(&mut self, xl: u8) {
+        if X::BITS == 64 {
+            self.set_field(34, 2, xl as u64)
+        }
+    }
+    /// Supervisor-mode big-endian data accesses, or [`None`] on RV32.
+    #[inline]
+    pub fn sbe(self) -> Option<bool> {
+        (X::BITS == 64).then(|| self.field(36, 1) != 0)
+    }
+    /// Supervisor-mode big-endian data accesses. Ignored on RV32.
+    pub fn set_sbe(&mut self, value: bool) {
+        if X::BITS == 64 {
+            self.set_field(36, 1, value as u64)
+        }
+    }
+    /// Machine-mode big-endian data accesses, or [`None`] on RV32.
+    #[inline]
+    pub fn mbe(self) -> Option<bool> {
+        (X::BITS == 64).then(|| self.field(37, 1) != 0)
+    }
+    /// Machine-mode big-endian data accesses. Ignored on RV32.
+    pub fn set_mbe(&mut self, value: bool) {
+        if X::BITS == 64 {
+            self.set_field(37, 1, value as u64)
+        }
+    }
+}
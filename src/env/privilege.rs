@@ -0,0 +1,30 @@
+/// A RISC-V privilege level, as encoded in fields such as `mstatus.MPP`.
+/// ```rust
+/// use rysk::env::Privilege;
+/// assert_eq!(Privilege::try_from(0), Ok(Privilege::User));
+/// assert_eq!(Privilege::try_from(3), Ok(Privilege::Machine));
+/// // Level 2 is reserved.
+/// assert_eq!(Privilege::try_from(2), Err(2));
+/// assert_eq!(Privilege::Supervisor as u8, 1);
+/// assert!(Privilege::User < Privilege::Machine);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[repr(u8)]
+pub enum Privilege {
+    User = 0,
+    Supervisor = 1,
+    Machine = 3
+}
+impl TryFrom<u8> for Privilege {
+    /// The rejected level, which is either reserved or wider than 2 bits.
+    type Error = u8;
+    #[inline]
+    fn try_from(level: u8) -> Result<Self, Self::Error> {
+        match level {
+            0 => Ok(Self::User),
+            1 => Ok(Self::Supervisor),
+            3 => Ok(Self::Machine),
+            _ => Err(level)
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::Xlen;
+
+/// The size in bytes of a reservation granule.
+///
+/// Reservations cover the naturally aligned doubleword containing the reserved
+/// address, large enough for both `lr.w` and `lr.d`. A store to any byte of
+/// the granule invalidates the reservation.
+pub const RESERVATION_GRANULE: usize = 8;
+
+/// The reservation set of a hart, registered by `lr` and consumed by `sc`.
+///
+/// Implementors provide the raw hooks, while [`Reservation::observe_store`]
+/// applies the rule that any store to the reserved granule clears it. It is
+/// the emulator's responsibility to report every store, including those from
+/// other harts and devices, for `sc` to fail as required.
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use rysk::{Addressable, env::{Reservation, ReservationSet, SparseMemory}};
+///
+/// let memory = SparseMemory::new();
+/// let mut hart = ReservationSet::new();
+/// let mut store = |hart: &mut ReservationSet<u32>, address, value| {
+///     hart.observe_store(address, 4);
+///     memory.write_u32(address, value).ok().unwrap();
+/// };
+/// // An sc that succeeds only if its reservation is still held.
+/// let mut sc = |hart: &mut ReservationSet<u32>, address, value| {
+///     let held = hart.check_reservation(address);
+///     hart.clear_reservation();
+///     if held {
+///         store(hart, address, value);
+///     }
+///     held
+/// };
+///
+/// // lr.w; sc.w
+/// hart.reserve(0x8000_0004);
+/// assert!(sc(&mut hart, 0x8000_0004, 1));
+/// // The reservation is consumed by the sc.
+/// assert!(!sc(&mut hart, 0x8000_0004, 2));
+///
+/// // lr.w; sw; sc.w
+/// hart.reserve(0x8000_0004);
+/// hart.observe_store(0x8000_0000, 4);
+/// assert!(!sc(&mut hart, 0x8000_0004, 3));
+///
+/// // Stores outside the granule leave the reservation intact.
+/// hart.reserve(0x8000_0004);
+/// hart.observe_store(0x8000_0008, 4);
+/// assert!(sc(&mut hart, 0x8000_0004, 4));
+///
+/// // A wide store spanning the whole granule clears it.
+/// hart.reserve(0x8000_0008);
+/// hart.observe_store(0x8000_0000, 24);
+/// assert!(!hart.check_reservation(0x8000_0008));
+///
+/// // As does a store wrapping past the top of the address space.
+/// hart.reserve(0);
+/// hart.observe_store(0xFFFF_FFFC, 8);
+/// assert_eq!(hart.reservation(), None);
+/// hart.reserve(0xFFFF_FFF8);
+/// hart.observe_store(0xFFFF_FFF0, 8);
+/// assert_eq!(hart.reservation(), Some(0xFFFF_FFF8));
+/// # }
+/// ```
+pub trait Reservation<X: Xlen> {
+    /// Register a reservation on the granule containing `addr`, replacing any
+    /// existing reservation.
+    fn reserve(&mut self, addr: X);
+    /// Returns `true` if a reservation is held on the granule containing
+    /// `addr`.
+    fn check_reservation(&self, addr: X) -> bool;
+    /// Return the base of the reserved granule, or [`None`] if no reservation
+    /// is held.
+    fn reservation(&self) -> Option<X>;
+    /// Invalidate any held reservation.
+    fn clear_reservation(&mut self);
+
+    /// Clear the reservation if a store of `width` bytes to `addr` overlaps the
+    /// reserved granule, wrapping at the XLEN boundary.
+    fn observe_store(&mut self, addr: X, width: usize) {
+        let granule = match self.reservation() {
+            Some(granule) => granule.to_u64(),
+            None => return
+        };
+        let start = addr.to_u64();
+        let top = u64::MAX >> (64 - X::BITS);
+        let last = start.wrapping_add(width.max(1) as u64 - 1) & top;
+        let overlaps = |start: u64, last: u64| start <= granule + (RESERVATION_GRANULE as u64 - 1) && granule <= last;
+        let hit = if last >= start {
+            overlaps(start, last)
+        } else {
+            overlaps(start, top) || overlaps(0, last)
+        };
+        if hit {
+            self.clear_reservation()
+        }
+    }
+}
+
+/// A single reservation, as held by each hart.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct ReservationSet<X: Xlen> {
+    granule: Option<X>
+}
+impl<X: Xlen> ReservationSet<X> {
+    /// Create a set holding no reservation.
+    #[inline]
+    pub fn new() -> Self {
+        Self { granule: None }
+    }
+}
+impl<X: Xlen> Reservation<X> for ReservationSet<X> {
+    #[inline]
+    fn reserve(&mut self, addr: X) {
+        self.granule = Some(granule(addr))
+    }
+    #[inline]
+    fn check_reservation(&self, addr: X) -> bool {
+        self.granule == Some(granule(addr))
+    }
+    #[inline]
+    fn reservation(&self) -> Option<X> {
+        self.granule
+    }
+    #[inline]
+    fn clear_reservation(&mut self) {
+        self.granule = None
+    }
+}
+
+/// Return the base of the reservation granule containing `addr`.
+#[inline]
+fn granule<X: Xlen>(addr: X) -> X {
+    X::from_u64(addr.to_u64() & !(RESERVATION_GRANULE as u64 - 1))
+}
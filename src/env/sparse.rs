@@ -0,0 +1,113 @@
+use alloc::{boxed::Box, collections::BTreeMap};
+use core::cell::RefCell;
+
+use crate::Xlen;
+
+use super::{wraps, Addressable, Cause};
+
+/// Size in bytes of each lazily allocated [`SparseMemory`] page.
+pub const PAGE_SIZE: usize = 4096;
+const PAGE_SHIFT: u32 = PAGE_SIZE.trailing_zeros();
+
+/// Sparse RAM spanning the entire address space.
+///
+/// Pages are allocated on first write and never-written memory reads as zero,
+/// so only the touched portion of the address space is backed by host memory.
+/// Accesses spanning a page boundary are split between the pages, while those
+/// that would wrap past the top of the address space raise an access fault,
+/// as [`read_bytes`](Addressable::read_bytes) and
+/// [`write_bytes`](Addressable::write_bytes) do.
+/// ```rust
+/// use rysk::{Addressable, Cause, env::SparseMemory};
+///
+/// let memory = SparseMemory::new();
+/// assert_eq!(memory.read_u64(0xFFFF_FFFF_0000_0000u64).ok(), Some(0));
+/// assert!(memory.write_u32(0xFFFF_FFFF_0000_0FFE, 0xDEAD_BEEF).is_ok());
+/// assert_eq!(memory.read_u32(0xFFFF_FFFF_0000_0FFE).ok(), Some(0xDEAD_BEEF));
+/// assert_eq!(memory.read_u16(0xFFFF_FFFF_0000_1000).ok(), Some(0xDEAD));
+/// assert_eq!(memory.pages(), 2);
+///
+/// assert_eq!(memory.read_u32(0xFFFF_FFFF_FFFF_FFFC).ok(), Some(0));
+/// assert!(memory.read_u32(0xFFFF_FFFF_FFFF_FFFE) == Err(Cause::LOAD_FAULT));
+/// assert!(memory.write_u16(0xFFFF_FFFF_FFFF_FFFF, 0) == Err(Cause::STORE_FAULT));
+/// assert_eq!(memory.pages(), 2);
+/// ```
+pub struct SparseMemory<X: Xlen> {
+    pages: RefCell<BTreeMap<X, Box<[u8; PAGE_SIZE]>>>
+}
+impl<X: Xlen> SparseMemory<X> {
+    /// Create an empty memory with no pages allocated.
+    pub fn new() -> Self {
+        Self {
+            pages: RefCell::new(BTreeMap::new())
+        }
+    }
+    /// Returns the number of pages that have been allocated.
+    pub fn pages(&self) -> usize {
+        self.pages.borrow().len()
+    }
+
+    #[inline]
+    fn split(address: u64) -> (X, usize) {
+        (X::from_u64(address >> PAGE_SHIFT), address as usize & (PAGE_SIZE - 1))
+    }
+    fn read<const N: usize>(&self, address: X) -> Result<[u8; N], Cause<X>> {
+        let base = address.to_u64();
+        if wraps::<X>(base, N) {
+            return Err(Cause::LOAD_FAULT);
+        }
+        let pages = self.pages.borrow();
+        let mut bytes = [0; N];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            let (page, offset) = Self::split(base + i as u64);
+            if let Some(page) = pages.get(&page) {
+                *byte = page[offset];
+            }
+        }
+        Ok(bytes)
+    }
+    fn write<const N: usize>(&self, address: X, bytes: [u8; N]) -> Result<(), Cause<X>> {
+        let base = address.to_u64();
+        if wraps::<X>(base, N) {
+            return Err(Cause::STORE_FAULT);
+        }
+        let mut pages = self.pages.borrow_mut();
+        for (i, byte) in bytes.into_iter().enumerate() {
+            let (page, offset) = Self::split(base + i as u64);
+            pages.entry(page).or_insert_with(|| Box::new([0; PAGE_SIZE]))[offset] = byte;
+        }
+        Ok(())
+    }
+}
+impl<X: Xlen> Default for SparseMemory<X> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<X: Xlen> Addressable<X> for SparseMemory<X> {
+    fn read_u8(&self, address: X) -> Result<u8, Cause<X>> {
+        self.read(address).map(u8::from_le_bytes)
+    }
+    fn read_u16(&self, address: X) -> Result<u16, Cause<X>> {
+        self.read(address).map(u16::from_le_bytes)
+    }
+    fn read_u32(&self, address: X) -> Result<u32, Cause<X>> {
+        self.read(address).map(u32::from_le_bytes)
+    }
+    fn read_u64(&self, address: X) -> Result<u64, Cause<X>> {
+        self.read(address).map(u64::from_le_bytes)
+    }
+
+    fn write_u8(&self, address: X, byte: u8) -> Result<(), Cause<X>> {
+        self.write(address, byte.to_le_bytes())
+    }
+    fn write_u16(&self, address: X, halfword: u16) -> Result<(), Cause<X>> {
+        self.write(address, halfword.to_le_bytes())
+    }
+    fn write_u32(&self, address: X, word: u32) -> Result<(), Cause<X>> {
+        self.write(address, word.to_le_bytes())
+    }
+    fn write_u64(&self, address: X, doubleword: u64) -> Result<(), Cause<X>> {
+        self.write(address, doubleword.to_le_bytes())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::Xlen;
+
+use super::Cause;
+
+/// A trap raised by [`Hart::step`](super::Hart::step), holding the values to
+/// be written to `mcause` and `mtval`.
+///
+/// Only the standard synchronous exceptions raised by the hart itself populate
+/// `tval`:
+/// - Misaligned fetches hold the PC. Instruction access faults hold the
+///   address of the faulting parcel, which is the PC unless only the second
+///   parcel of a standard instruction faulted.
+/// - Misaligned loads and stores and load and store faults hold the faulting
+///   effective address.
+/// - Illegal instructions hold the instruction bits, which for a compressed
+///   instruction are only the 16-bit parcel.
+/// - Breakpoints hold the PC of the `ebreak`.
+///
+/// Everything else, including environment calls, has a `tval` of zero.
+/// ```rust
+/// # #[cfg(feature = "alloc")] {
+/// use rysk::{Cause, Hart, env::{BasicHart, Memory, TrapInfo}};
+///
+/// // lw a0,-4(zero)
+/// let memory = Memory::from_vec(0x8000_0000u32, vec![0x03, 0x25, 0xC0, 0xFF]);
+/// let mut hart = BasicHart::new(memory, 0x8000_0000);
+/// assert!(hart.step() == Err(TrapInfo { cause: Cause::LOAD_FAULT, tval: 0xFFFF_FFFC }));
+/// assert_eq!(hart.pc(), 0x8000_0000);
+///
+/// // addi a0,a1,4 straddling the end of memory faults on its second parcel.
+/// let memory = Memory::from_vec(0x8000_0000u32, vec![0x13, 0x85]);
+/// let mut hart = BasicHart::new(memory, 0x8000_0000);
+/// assert!(hart.step() == Err(TrapInfo { cause: Cause::FETCH_FAULT, tval: 0x8000_0002 }));
+/// assert_eq!(hart.pc(), 0x8000_0000);
+/// # }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TrapInfo<X: Xlen> {
+    /// The cause of the trap.
+    pub cause: Cause<X>,
+    /// The exception-specific trap value.
+    pub tval: X
+}
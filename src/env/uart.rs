@@ -0,0 +1,169 @@
+use core::cell::Cell;
+
+use crate::Xlen;
+
+use super::{Addressable, Cause};
+
+/// A minimal 16550-style UART.
+///
+/// Bytes written to the transmit holding register are passed to the host
+/// provided `tx` sink. The host delivers input with [`Uart::receive`], which
+/// holds a single byte until the guest reads it.
+///
+/// Only the byte-wide register file is modelled. Wider accesses operate on
+/// the low byte of the register at the given address.
+/// ```rust
+/// use core::cell::RefCell;
+/// use rysk::{Addressable, env::Uart};
+///
+/// let output = RefCell::new(Vec::new());
+/// let uart = Uart::new(0x1000_0000u32, |byte| output.borrow_mut().push(byte));
+/// for &byte in b"hi" {
+///     assert!(uart.write_u8(0x1000_0000, byte).is_ok());
+/// }
+/// assert_eq!(&*output.borrow(), b"hi");
+///
+/// // Enable the receive interrupt and deliver a byte from the host.
+/// assert!(uart.write_u8(0x1000_0001, 0b1).is_ok());
+/// assert!(uart.receive(b'x'));
+/// assert!(uart.interrupt());
+/// // The line status register reports data ready.
+/// assert_eq!(uart.read_u8(0x1000_0005).ok().map(|lsr| lsr & 0b1), Some(0b1));
+/// assert_eq!(uart.read_u8(0x1000_0000).ok(), Some(b'x'));
+/// assert!(!uart.interrupt());
+/// ```
+pub struct Uart<X: Xlen, F: Fn(u8)> {
+    base: X,
+    tx: F,
+    rx: Cell<Option<u8>>,
+    ier: Cell<u8>,
+    lcr: Cell<u8>,
+    divisor: Cell<u16>,
+    scratch: Cell<u8>
+}
+impl<X: Xlen, F: Fn(u8)> Uart<X, F> {
+    /// Receiver buffer (read) and transmitter holding (write) register.
+    pub const RBR_THR: u64 = 0;
+    /// Interrupt enable register.
+    pub const IER: u64 = 1;
+    /// Interrupt identification (read) and FIFO control (write) register.
+    pub const IIR_FCR: u64 = 2;
+    /// Line control register.
+    pub const LCR: u64 = 3;
+    /// Modem control register.
+    pub const MCR: u64 = 4;
+    /// Line status register.
+    pub const LSR: u64 = 5;
+    /// Modem status register.
+    pub const MSR: u64 = 6;
+    /// Scratch register.
+    pub const SCR: u64 = 7;
+
+    /// IER bit enabling the received data available interrupt.
+    pub const IER_RX_AVAILABLE: u8 = 1 << 0;
+    /// LCR bit selecting the divisor latch at offsets 0 and 1.
+    pub const LCR_DLAB: u8 = 1 << 7;
+    /// LSR bit indicating a byte is waiting in the receiver buffer.
+    pub const LSR_DATA_READY: u8 = 1 << 0;
+    /// LSR bit indicating the transmitter holding register is empty.
+    pub const LSR_THR_EMPTY: u8 = 1 << 5;
+    /// LSR bit indicating the transmitter is idle.
+    pub const LSR_TX_EMPTY: u8 = 1 << 6;
+
+    /// Create a UART with its register file at `base`, sending transmitted
+    /// bytes to `tx`.
+    pub fn new(base: X, tx: F) -> Self {
+        Self {
+            base,
+            tx,
+            rx: Cell::new(None),
+            ier: Cell::new(0),
+            lcr: Cell::new(0),
+            divisor: Cell::new(0),
+            scratch: Cell::new(0)
+        }
+    }
+    /// Deliver a byte from the host to the receiver.
+    ///
+    /// Returns `false`, dropping the byte, if the guest has not yet read the
+    /// previously received byte.
+    pub fn receive(&self, byte: u8) -> bool {
+        if self.rx.get().is_some() {
+            false
+        } else {
+            self.rx.set(Some(byte));
+            true
+        }
+    }
+    /// Returns `true` if the UART is asserting its interrupt line.
+    ///
+    /// The line is raised while received data is waiting and the receive
+    /// interrupt is enabled in IER.
+    pub fn interrupt(&self) -> bool {
+        self.rx.get().is_some() && self.ier.get() & Self::IER_RX_AVAILABLE != 0
+    }
+
+    #[inline]
+    fn dlab(&self) -> bool {
+        self.lcr.get() & Self::LCR_DLAB != 0
+    }
+    fn read_register(&self, address: X) -> u8 {
+        match address.to_u64().wrapping_sub(self.base.to_u64()) {
+            Self::RBR_THR if self.dlab() => self.divisor.get() as u8,
+            Self::RBR_THR => self.rx.take().unwrap_or(0),
+            Self::IER if self.dlab() => (self.divisor.get() >> 8) as u8,
+            Self::IER => self.ier.get(),
+            // FIFOs are not modelled; report either no interrupt or data available.
+            Self::IIR_FCR => if self.interrupt() { 0b0100 } else { 0b0001 },
+            Self::LCR => self.lcr.get(),
+            Self::LSR => {
+                let ready = if self.rx.get().is_some() { Self::LSR_DATA_READY } else { 0 };
+                ready | Self::LSR_THR_EMPTY | Self::LSR_TX_EMPTY
+            },
+            Self::SCR => self.scratch.get(),
+            _ => 0
+        }
+    }
+    fn write_register(&self, address: X, byte: u8) {
+        match address.to_u64().wrapping_sub(self.base.to_u64()) {
+            Self::RBR_THR if self.dlab() => self.divisor.set(self.divisor.get() & 0xFF00 | byte as u16),
+            Self::RBR_THR => (self.tx)(byte),
+            Self::IER if self.dlab() => self.divisor.set(self.divisor.get() & 0x00FF | (byte as u16) << 8),
+            Self::IER => self.ier.set(byte & 0x0F),
+            Self::LCR => self.lcr.set(byte),
+            Self::SCR => self.scratch.set(byte),
+            _ => ()
+        }
+    }
+}
+impl<X: Xlen, F: Fn(u8)> Addressable<X> for Uart<X, F> {
+    fn read_u8(&self, address: X) -> Result<u8, Cause<X>> {
+        Ok(self.read_register(address))
+    }
+    fn read_u16(&self, address: X) -> Result<u16, Cause<X>> {
+        Ok(self.read_register(address) as u16)
+    }
+    fn read_u32(&self, address: X) -> Result<u32, Cause<X>> {
+        Ok(self.read_register(address) as u32)
+    }
+    fn read_u64(&self, address: X) -> Result<u64, Cause<X>> {
+        Ok(self.read_register(address) as u64)
+    }
+
+    fn write_u8(&self, address: X, byte: u8) -> Result<(), Cause<X>> {
+        self.write_register(address, byte);
+        Ok(())
+    }
+    fn write_u16(&self, address: X, halfword: u16) -> Result<(), Cause<X>> {
+        self.write_register(address, halfword as u8);
+        Ok(())
+    }
+    fn write_u32(&self, address: X, word: u32) -> Result<(), Cause<X>> {
+        self.write_register(address, word as u8);
+        Ok(())
+    }
+    fn write_u64(&self, address: X, doubleword: u64) -> Result<(), Cause<X>> {
+        self.write_register(address, doubleword as u8);
+        Ok(())
+    }
+}
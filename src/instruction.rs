@@ -1,3 +1,58 @@
+use crate::Xlen;
+
+mod asm;
+mod decoded;
+mod encode;
+mod expand;
+mod extensions;
+mod format;
+mod iter;
+mod pattern;
+pub use decoded::{
+    AluOp, AmoOp, AmoOrdering, BranchKind, CsrOp, CsrSource, Decoded, LoadWidth, MulDivOp, RoundingMode, StoreWidth,
+    SystemOp
+};
+pub use encode::EncodeError;
+pub use extensions::Extensions;
+pub use format::{CFormat, Format};
+pub use iter::{iter, InstructionIter};
+pub use pattern::InstructionPattern;
+
+/// Return the length in bytes of the instruction beginning with
+/// `first_parcel`, following the base variable-length encoding scheme.
+///
+/// Lengths beyond 32 bits are reported even though such instructions cannot be
+/// represented by [`Instruction`]. The encoding reserved for instructions of
+/// 192 bits or more has no defined length and returns 0.
+/// ```rust
+/// use rysk::instruction::encoded_length;
+/// // c.jr ra
+/// assert_eq!(encoded_length(0x8082), 2);
+/// // addi a0,a0,-1
+/// assert_eq!(encoded_length(0x0513), 4);
+/// assert_eq!(encoded_length(0x001F), 6);
+/// assert_eq!(encoded_length(0x003F), 8);
+/// assert_eq!(encoded_length(0x007F), 10);
+/// assert_eq!(encoded_length(0x607F), 22);
+/// assert_eq!(encoded_length(0x707F), 0);
+/// ```
+pub fn encoded_length(first_parcel: u16) -> usize {
+    if first_parcel & 0b11 != 0b11 {
+        2
+    } else if first_parcel & 0b1_1100 != 0b1_1100 {
+        4
+    } else if first_parcel & 0b10_0000 == 0 {
+        6
+    } else if first_parcel & 0b100_0000 == 0 {
+        8
+    } else {
+        match (first_parcel >> 12) & 0b111 {
+            0b111 => 0,
+            n => 10 + 2 * n as usize
+        }
+    }
+}
+
 /// A RISC-V standard or compressed machine instruction.
 /// ```rust
 /// use rysk::{Instruction, Register};
@@ -40,9 +95,13 @@
 /// assert_eq!(j.opcode(), 0b1101111);
 /// assert_eq!(j.rd(), Register::X7);
 /// assert_eq!(j.j_immediate(), 0xDEAD4);
+///
+/// // Instructions compare and hash by their raw value.
+/// assert!(Instruction::new(0x2d5de3ef) == j);
+/// assert!(Instruction::new(0x2d5de3ef) != u);
 /// ```
-#[derive(Clone, Copy)]
-pub struct Instruction(u32);
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Instruction(pub(crate) u32);
 impl Instruction {
     /// Creates a new 32-bit standard instruction.
     #[inline]
@@ -62,6 +121,86 @@ impl Instruction {
             (Self(u32::from_le_bytes([a, b, c, d])), false)
         }
     }
+    /// Like [`Instruction::from_parcels`], but with each parcel holding its
+    /// bytes in big-endian order, as read from a big-endian dump.
+    ///
+    /// RISC-V instructions are always stored little-endian regardless of the
+    /// data endianness, so this only normalizes the input and the parcels are
+    /// still taken in fetch order.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // addi a0,a1,4 stored as [0x13, 0x85, 0x45, 0x00]
+    /// let le = Instruction::from_parcels([0x8513, 0x0045]);
+    /// let be = Instruction::from_parcels_be([0x1385, 0x4500]);
+    /// assert!(le == be);
+    /// assert_eq!(be.0.raw(), 0x00458513);
+    /// // c.jr ra stored as [0x82, 0x80]
+    /// assert!(Instruction::from_parcels_be([0x8280, 0]) == Instruction::from_parcels([0x8082, 0]));
+    /// ```
+    #[inline]
+    pub fn from_parcels_be(parcels: [u16; 2]) -> (Self, bool) {
+        Self::from_parcels(parcels.map(u16::swap_bytes))
+    }
+    /// Takes little-endian instruction bytes and returns the instruction and
+    /// the number of bytes it occupies, or [`None`] if `bytes` is too short.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.jr ra
+    /// let (i, length) = Instruction::from_bytes(&[0x82, 0x80, 0xFF]).unwrap();
+    /// assert!(i.compressed());
+    /// assert_eq!(length, 2);
+    /// // addi a0,a1,4
+    /// let (i, length) = Instruction::from_bytes(&[0x13, 0x85, 0x45, 0x00]).unwrap();
+    /// assert_eq!((i.rd(), i.i_immediate(), length), (rysk::Register::X10, 4, 4));
+    /// assert!(Instruction::from_bytes(&[0x13, 0x85, 0x45]).is_none());
+    /// assert!(Instruction::from_bytes(&[0x82]).is_none());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Option<(Self, usize)> {
+        let low = u16::from_le_bytes([*bytes.first()?, *bytes.get(1)?]);
+        let high = if low & 0b11 == 0b11 {
+            u16::from_le_bytes([*bytes.get(2)?, *bytes.get(3)?])
+        } else {
+            0
+        };
+        match Self::from_parcels([low, high]) {
+            (i, true) => Some((i, 2)),
+            (i, false) => Some((i, 4))
+        }
+    }
+    /// Return the raw instruction bits, with the upper parcel zero for a
+    /// compressed instruction.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// assert_eq!(Instruction::new(0x4d258fe7).raw(), 0x4d258fe7);
+    /// assert_eq!(Instruction::from_parcels([0x8082, 0xFFFF]).0.raw(), 0x8082);
+    /// ```
+    #[inline]
+    pub fn raw(self) -> u32 {
+        self.0
+    }
+    /// Return the instruction as little-endian bytes.
+    ///
+    /// A compressed instruction only occupies the first two bytes, with the
+    /// rest zero. See [`Instruction::to_le_bytes_compressed`].
+    /// ```rust
+    /// use rysk::Instruction;
+    /// assert_eq!(Instruction::new(0x00458513).to_le_bytes(), [0x13, 0x85, 0x45, 0x00]);
+    /// assert_eq!(Instruction::from_parcels([0x8082, 0]).0.to_le_bytes(), [0x82, 0x80, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn to_le_bytes(self) -> [u8; 4] {
+        self.0.to_le_bytes()
+    }
+    /// Return the single parcel of a compressed instruction as little-endian
+    /// bytes. Standard instructions are truncated to their lower parcel.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// assert_eq!(Instruction::from_parcels([0x8082, 0]).0.to_le_bytes_compressed(), [0x82, 0x80]);
+    /// ```
+    #[inline]
+    pub fn to_le_bytes_compressed(self) -> [u8; 2] {
+        (self.0 as u16).to_le_bytes()
+    }
     /// Returns `true` if the instruction is a compressed 16-bit instruction.
     #[inline]
     pub fn compressed(self) -> bool {
@@ -72,6 +211,19 @@ impl Instruction {
     pub fn standard(self) -> bool {
         self.0 & 0b11 == 0b11
     }
+    /// Return the fallthrough address of the instruction at `pc`, advancing
+    /// by 2 bytes for a compressed instruction and 4 for a standard one.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// assert_eq!(Instruction::new(0x4d258fe7).next_pc(0x8000_0000u32), 0x8000_0004);
+    /// assert_eq!(Instruction::from_parcels([0x8082, 0]).0.next_pc(0x8000_0000u64), 0x8000_0002);
+    /// assert_eq!(Instruction::new(0x4d258fe7).next_pc(u32::MAX - 1), 2);
+    /// ```
+    #[inline]
+    pub fn next_pc<X: Xlen>(self, pc: X) -> X {
+        let length = if self.compressed() { 2 } else { 4 };
+        X::from_u64(pc.to_u64().wrapping_add(length))
+    }
 
     /// Return the standard opcode.
     /// ```rust
@@ -81,6 +233,21 @@ impl Instruction {
     pub fn opcode(self) -> u8 {
         (self.0 & 0b0111_1111) as u8
     }
+    /// Return the named standard opcode, or `None` for compressed instructions
+    /// and reserved opcodes.
+    /// ```rust
+    /// use rysk::{Instruction, Opcode};
+    /// // jalr x31,1234(x11)
+    /// assert_eq!(Instruction::new(0x4d258fe7).decoded_opcode(), Some(Opcode::Jalr));
+    /// // fmadd.s fa0,fa1,fa2,fa3
+    /// assert_eq!(Instruction::new(0x68c5f543).decoded_opcode(), Some(Opcode::Madd));
+    /// // c.addi a0,-1
+    /// assert_eq!(Instruction::from_parcels([0x157D, 0]).0.decoded_opcode(), None);
+    /// ```
+    #[inline]
+    pub fn decoded_opcode(self) -> Option<Opcode> {
+        Opcode::from_u8(self.opcode())
+    }
     /// Return the compressed opcode.
     #[inline]
     pub fn op(self) -> u8 {
@@ -96,6 +263,63 @@ impl Instruction {
     pub fn funct7(self) -> u8 {
         ((self.0 & 0xFE00_0000) >> 25) as u8
     }
+    /// Return the R4-type funct2, selecting the floating-point format of a
+    /// fused multiply-add.
+    ///
+    /// R4-type is only used by the MADD, MSUB, NMSUB and NMADD opcodes
+    /// (`fmadd`, `fmsub`, `fnmsub` and `fnmadd`), where the third source
+    /// register replaces the upper bits of funct7.
+    /// ```rust
+    /// use rysk::{Instruction, Register};
+    /// // fmadd.s fa0,fa1,fa2,fa3
+    /// let fmadd = Instruction::new(0x68c5f543);
+    /// assert_eq!(fmadd.opcode(), 0b1000011);
+    /// assert_eq!(fmadd.r4_funct2(), 0b00);
+    /// assert_eq!(fmadd.rs3(), Register::X13);
+    /// // fmadd.d fa0,fa1,fa2,ft11,rtz
+    /// assert_eq!(Instruction::new(0xfac59543).r4_funct2(), 0b01);
+    /// ```
+    #[inline]
+    pub fn r4_funct2(self) -> u8 {
+        ((self.0 & 0x0600_0000) >> 25) as u8
+    }
+    /// Return the 5-bit RV32 shift amount of a shift-immediate instruction.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // srai x17,x7,19
+    /// assert_eq!(Instruction::new(0x4133d893).shamt5(), 19);
+    /// // slli t0,t1,3
+    /// assert_eq!(Instruction::new(0x00331293).shamt5(), 3);
+    /// ```
+    #[inline]
+    pub fn shamt5(self) -> u8 {
+        ((self.0 & 0x01F0_0000) >> 20) as u8
+    }
+    /// Return the 6-bit RV64 shift amount of a shift-immediate instruction.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // slli a0,a1,63
+    /// assert_eq!(Instruction::new(0x03f59513).shamt6(), 63);
+    /// // srai a0,a1,40
+    /// assert_eq!(Instruction::new(0x4285d513).shamt6(), 40);
+    /// ```
+    #[inline]
+    pub fn shamt6(self) -> u8 {
+        ((self.0 & 0x03F0_0000) >> 20) as u8
+    }
+    /// Returns `true` if bit 30 selects an arithmetic rather than logical
+    /// right shift.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // srai a0,a1,40
+    /// assert!(Instruction::new(0x4285d513).shift_arithmetic());
+    /// // slli a0,a1,63
+    /// assert!(!Instruction::new(0x03f59513).shift_arithmetic());
+    /// ```
+    #[inline]
+    pub fn shift_arithmetic(self) -> bool {
+        self.0 & 0x4000_0000 != 0
+    }
     /// Return the compressed funct2.
     #[inline]
     pub fn compressed_funct2(self) -> u8 {
@@ -141,6 +365,14 @@ impl Instruction {
             ((self.0 & 0x01F0_0000) >> 20) as u8
         )}
     }
+    /// Return the R4-type third source register number.
+    #[inline]
+    pub fn rs3(self) -> Register {
+        // Safety: The register number is 5-bit and cannot exceed 31.
+        unsafe { Register::new_unchecked(
+            ((self.0 & 0xF800_0000) >> 27) as u8
+        )}
+    }
     /// Return the first compressed full-sized source register number.
     #[inline]
     pub fn compressed_rs1(self) -> Register {
@@ -174,6 +406,54 @@ impl Instruction {
         )}
     }
 
+    /// Return the floating-point destination register number.
+    /// ```rust
+    /// use rysk::{FRegister, Instruction};
+    /// // fadd.s ft0,fa1,fs2
+    /// let fadd = Instruction::new(0x0125f053);
+    /// assert_eq!(fadd.frd(), FRegister::F0);
+    /// assert_eq!(fadd.frs1(), FRegister::F11);
+    /// assert_eq!(fadd.frs2(), FRegister::F18);
+    /// // fmadd.d fa0,fa1,fa2,ft11,rtz
+    /// let fmadd = Instruction::new(0xfac59543);
+    /// assert_eq!(fmadd.frd(), FRegister::F10);
+    /// assert_eq!(fmadd.frs1(), FRegister::F11);
+    /// assert_eq!(fmadd.frs2(), FRegister::F12);
+    /// assert_eq!(fmadd.frs3(), FRegister::F31);
+    /// ```
+    #[inline]
+    pub fn frd(self) -> FRegister {
+        // Safety: The register number is 5-bit and cannot exceed 31.
+        unsafe { FRegister::new_unchecked(
+            ((self.0 & 0x0000_0F80) >> 7) as u8
+        )}
+    }
+    /// Return the first floating-point source register number.
+    #[inline]
+    pub fn frs1(self) -> FRegister {
+        // Safety: The register number is 5-bit and cannot exceed 31.
+        unsafe { FRegister::new_unchecked(
+            ((self.0 & 0x000F_8000) >> 15) as u8
+        )}
+    }
+    /// Return the second floating-point source register number.
+    #[inline]
+    pub fn frs2(self) -> FRegister {
+        // Safety: The register number is 5-bit and cannot exceed 31.
+        unsafe { FRegister::new_unchecked(
+            ((self.0 & 0x01F0_0000) >> 20) as u8
+        )}
+    }
+    /// Return the third floating-point source register number of an R4-type
+    /// fused multiply-add.
+    #[inline]
+    pub fn frs3(self) -> FRegister {
+        // Safety: The register number is 5-bit and cannot exceed 31.
+        unsafe { FRegister::new_unchecked(
+            ((self.0 & 0xF800_0000) >> 27) as u8
+        )}
+    }
+
     /// Return the I-type immediate value.
     #[inline]
     pub fn i_immediate(self) -> u32 {
@@ -195,13 +475,522 @@ impl Instruction {
         self.0 & 0xFFFF_F000
     }
     /// Return the J-type immediate value.
+    /// ```rust
+    /// // jal ra,2048
+    /// assert_eq!(rysk::Instruction::new(0x001000ef).j_immediate(), 2048);
+    /// ```
     pub fn j_immediate(self) -> u32 {
         (((self.0 as i32) >> 11) as u32 & 0xFFF0_0000) |
         (self.0                         & 0x000F_F000) |
-        ((self.0 >> 8)                  & 0b1000_0000_0000) |
+        ((self.0 >> 9)                  & 0b1000_0000_0000) |
         ((self.0 >> 20)                 & 0b0111_1111_1110)
     }
-    
+    /// Return the I-type immediate value as a signed integer.
+    #[inline]
+    pub fn i_immediate_signed(self) -> i32 {
+        self.i_immediate() as i32
+    }
+    /// Return the S-type immediate value as a signed integer.
+    #[inline]
+    pub fn s_immediate_signed(self) -> i32 {
+        self.s_immediate() as i32
+    }
+    /// Return the B-type immediate value as a signed integer.
+    /// ```rust
+    /// // bgeu x10,x8,-4
+    /// assert_eq!(rysk::Instruction::new(0xfe857ee3).b_immediate_signed(), -4);
+    /// ```
+    #[inline]
+    pub fn b_immediate_signed(self) -> i32 {
+        self.b_immediate() as i32
+    }
+    /// Return the J-type immediate value as a signed integer.
+    #[inline]
+    pub fn j_immediate_signed(self) -> i32 {
+        self.j_immediate() as i32
+    }
+    /// Return the I-type immediate value sign-extended to XLEN.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // addi a0,a1,-5
+    /// let addi = Instruction::new(0xffb58513);
+    /// assert_eq!(addi.i_immediate_x::<u64>(), 0xFFFF_FFFF_FFFF_FFFB);
+    /// assert_eq!(addi.i_immediate_x::<u32>(), 0xFFFF_FFFB);
+    /// // addi a0,a1,4
+    /// assert_eq!(Instruction::new(0x00458513).i_immediate_x::<u64>(), 4);
+    /// ```
+    #[inline]
+    pub fn i_immediate_x<X: Xlen>(self) -> X {
+        X::sign_extend(X::from_u64(self.i_immediate() as u64), 12)
+    }
+    /// Return the S-type immediate value sign-extended to XLEN.
+    /// ```rust
+    /// // sw s0,-8(sp)
+    /// assert_eq!(rysk::Instruction::new(0xfe812c23).s_immediate_x::<u64>(), -8i64 as u64);
+    /// ```
+    #[inline]
+    pub fn s_immediate_x<X: Xlen>(self) -> X {
+        X::sign_extend(X::from_u64(self.s_immediate() as u64), 12)
+    }
+    /// Return the B-type immediate value sign-extended to XLEN.
+    /// ```rust
+    /// // bgeu x10,x8,-4
+    /// assert_eq!(rysk::Instruction::new(0xfe857ee3).b_immediate_x::<u64>(), -4i64 as u64);
+    /// ```
+    #[inline]
+    pub fn b_immediate_x<X: Xlen>(self) -> X {
+        X::sign_extend(X::from_u64(self.b_immediate() as u64), 13)
+    }
+    /// Return the U-type immediate value sign-extended to XLEN, as loaded by
+    /// `lui` on RV64.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // lui a5,0xdead4
+    /// let lui = Instruction::new(0xdead47b7);
+    /// assert_eq!(lui.u_immediate_x::<u64>(), 0xFFFF_FFFF_DEAD_4000);
+    /// assert_eq!(lui.u_immediate_x::<u32>(), 0xDEAD_4000);
+    /// ```
+    #[inline]
+    pub fn u_immediate_x<X: Xlen>(self) -> X {
+        X::sign_extend(X::from_u64(self.u_immediate() as u64), 32)
+    }
+    /// Return the J-type immediate value sign-extended to XLEN.
+    /// ```rust
+    /// // jal ra,-4
+    /// assert_eq!(rysk::Instruction::new(0xffdff0ef).j_immediate_x::<u64>(), -4i64 as u64);
+    /// ```
+    #[inline]
+    pub fn j_immediate_x<X: Xlen>(self) -> X {
+        X::sign_extend(X::from_u64(self.j_immediate() as u64), 21)
+    }
+    /// Return the target of a conditional branch at `pc`, wrapping at the XLEN
+    /// boundary.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // bgeu x10,x8,-4
+    /// let bgeu = Instruction::new(0xfe857ee3);
+    /// assert_eq!(bgeu.branch_target(0x8000_0000u32), 0x7FFF_FFFC);
+    /// assert_eq!(bgeu.branch_target(0x8000_0000u64), 0x7FFF_FFFC);
+    /// assert_eq!(bgeu.branch_target(0u32), 0xFFFF_FFFC);
+    /// assert_eq!(bgeu.branch_target(0u64), 0xFFFF_FFFF_FFFF_FFFC);
+    /// ```
+    #[inline]
+    pub fn branch_target<X: Xlen>(self, pc: X) -> X {
+        X::from_u64(pc.to_u64().wrapping_add(self.b_immediate_x::<X>().to_u64()))
+    }
+    /// Return the target of a `jal` at `pc`, wrapping at the XLEN boundary.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // jal x7,0xDEAD4
+    /// let jal = Instruction::new(0x2d5de3ef);
+    /// assert_eq!(jal.jal_target(0x8000_0000u32), 0x800D_EAD4);
+    /// assert_eq!(jal.jal_target(0xFFFF_FFFF_FFFF_0000u64), 0xCEAD4);
+    /// // jal ra,-4
+    /// assert_eq!(Instruction::new(0xffdff0ef).jal_target(0x1000u64), 0xFFC);
+    /// ```
+    #[inline]
+    pub fn jal_target<X: Xlen>(self, pc: X) -> X {
+        X::from_u64(pc.to_u64().wrapping_add(self.j_immediate_x::<X>().to_u64()))
+    }
+
+    /// Return the 12-bit CSR address of a Zicsr instruction.
+    ///
+    /// Only meaningful for the SYSTEM opcode, where the address occupies the
+    /// I-type immediate bits.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // csrrw a0,mstatus,a1
+    /// assert_eq!(Instruction::new(0x30059573).csr(), 0x300);
+    /// // csrrwi a0,0xFFF,31
+    /// assert_eq!(Instruction::new(0xffffd573).csr(), 0xFFF);
+    /// ```
+    #[inline]
+    pub fn csr(self) -> u16 {
+        (self.0 >> 20) as u16
+    }
+    /// Return the 5-bit zero-extended immediate of a `csrrwi`, `csrrsi` or
+    /// `csrrci`, held in the `rs1` field.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // csrrwi a0,0xFFF,31
+    /// assert_eq!(Instruction::new(0xffffd573).csr_zimm(), 31);
+    /// ```
+    #[inline]
+    pub fn csr_zimm(self) -> u8 {
+        ((self.0 & 0x000F_8000) >> 15) as u8
+    }
+
+    /// Return the predecessor set of a `fence`, with bits `I`, `O`, `R` and
+    /// `W` from most to least significant.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // fence rw,rw
+    /// let fence = Instruction::new(0x0330000f);
+    /// assert_eq!((fence.fence_fm(), fence.fence_pred(), fence.fence_succ()), (0b0000, 0b0011, 0b0011));
+    /// // fence i,o
+    /// let fence = Instruction::new(0x0840000f);
+    /// assert_eq!((fence.fence_fm(), fence.fence_pred(), fence.fence_succ()), (0b0000, 0b1000, 0b0100));
+    /// ```
+    #[inline]
+    pub fn fence_pred(self) -> u8 {
+        ((self.0 & 0x0F00_0000) >> 24) as u8
+    }
+    /// Return the successor set of a `fence`, with bits `I`, `O`, `R` and
+    /// `W` from most to least significant.
+    #[inline]
+    pub fn fence_succ(self) -> u8 {
+        ((self.0 & 0x00F0_0000) >> 20) as u8
+    }
+    /// Return the fence mode of a `fence`, `0b1000` for `fence.tso` and zero
+    /// for a normal fence.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // fence.tso
+    /// let fence = Instruction::new(0x8330000f);
+    /// assert_eq!((fence.fence_fm(), fence.fence_pred(), fence.fence_succ()), (0b1000, 0b0011, 0b0011));
+    /// ```
+    #[inline]
+    pub fn fence_fm(self) -> u8 {
+        ((self.0 & 0xF000_0000) >> 28) as u8
+    }
+
+    /// Return the atomic operation of an A extension instruction, held in the
+    /// upper five bits of funct7.
+    /// ```rust
+    /// use rysk::{AmoOrdering, Instruction};
+    /// // amoadd.w.aqrl a0,a1,(a2)
+    /// let amo = Instruction::new(0x06b6252f);
+    /// assert_eq!(amo.amo_funct5(), 0b00000);
+    /// assert!(amo.amo_aq() && amo.amo_rl());
+    /// assert_eq!(amo.amo_ordering(), AmoOrdering::AcquireRelease);
+    /// // lr.w.aq t0,(a0)
+    /// let lr = Instruction::new(0x140522af);
+    /// assert_eq!(lr.amo_funct5(), 0b00010);
+    /// assert_eq!(lr.amo_ordering(), AmoOrdering::Acquire);
+    /// ```
+    #[inline]
+    pub fn amo_funct5(self) -> u8 {
+        ((self.0 & 0xF800_0000) >> 27) as u8
+    }
+    /// Returns `true` if the atomic instruction has acquire semantics.
+    #[inline]
+    pub fn amo_aq(self) -> bool {
+        self.0 & 0x0400_0000 != 0
+    }
+    /// Returns `true` if the atomic instruction has release semantics.
+    #[inline]
+    pub fn amo_rl(self) -> bool {
+        self.0 & 0x0200_0000 != 0
+    }
+    /// Return the memory ordering of an atomic instruction.
+    #[inline]
+    pub fn amo_ordering(self) -> AmoOrdering {
+        AmoOrdering::from_bits(self.amo_aq(), self.amo_rl())
+    }
+
+    /// Return the static rounding mode of a floating-point instruction, held
+    /// in funct3.
+    /// ```rust
+    /// use rysk::{Instruction, RoundingMode};
+    /// // fadd.s ft0,fa1,fs2,<mode>
+    /// let fadd = |funct3: u32| Instruction::new(0x0125_8053 | funct3 << 12).rounding_mode();
+    /// assert_eq!(fadd(0b000), RoundingMode::Rne);
+    /// assert_eq!(fadd(0b001), RoundingMode::Rtz);
+    /// assert_eq!(fadd(0b010), RoundingMode::Rdn);
+    /// assert_eq!(fadd(0b011), RoundingMode::Rup);
+    /// assert_eq!(fadd(0b100), RoundingMode::Rmm);
+    /// assert_eq!(fadd(0b101), RoundingMode::Reserved(0b101));
+    /// assert_eq!(fadd(0b110), RoundingMode::Reserved(0b110));
+    /// assert_eq!(fadd(0b111), RoundingMode::Dynamic);
+    /// ```
+    #[inline]
+    pub fn rounding_mode(self) -> RoundingMode {
+        match self.funct3() {
+            0b000 => RoundingMode::Rne,
+            0b001 => RoundingMode::Rtz,
+            0b010 => RoundingMode::Rdn,
+            0b011 => RoundingMode::Rup,
+            0b100 => RoundingMode::Rmm,
+            0b111 => RoundingMode::Dynamic,
+            rm => RoundingMode::Reserved(rm)
+        }
+    }
+
+    /// Return the sign-extended 6-bit CI-type immediate, as used by `c.addi`,
+    /// `c.li` and `c.andi`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.addi a0,-1
+    /// assert_eq!(Instruction::from_parcels([0x157D, 0]).0.ci_immediate(), -1i32 as u32);
+    /// // c.li a0,31
+    /// assert_eq!(Instruction::from_parcels([0x457D, 0]).0.ci_immediate(), 31);
+    /// ```
+    pub fn ci_immediate(self) -> u32 {
+        ((((self.0 << 19) as i32) >> 26) as u32 & !0b1_1111) |
+        ((self.0 >> 2)                          & 0b1_1111)
+    }
+    /// Return the zero-extended CSS-type word offset of a `c.swsp`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.swsp a2,252(sp)
+    /// assert_eq!(Instruction::from_parcels([0xDFB2, 0]).0.css_immediate(), 252);
+    /// ```
+    pub fn css_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b0011_1100) |
+        ((self.0 >> 1) & 0b1100_0000)
+    }
+    /// Return the zero-extended CIW-type immediate of a `c.addi4spn`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.addi4spn s1,sp,1020
+    /// assert_eq!(Instruction::from_parcels([0x1FE4, 0]).0.ciw_immediate(), 1020);
+    /// ```
+    pub fn ciw_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b00_0011_0000) |
+        ((self.0 >> 1) & 0b11_1100_0000) |
+        ((self.0 >> 4) & 0b00_0000_0100) |
+        ((self.0 >> 2) & 0b00_0000_1000)
+    }
+    /// Return the zero-extended CL-type word offset of a `c.lw`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.lw a5,124(a4)
+    /// assert_eq!(Instruction::from_parcels([0x5F7C, 0]).0.cl_immediate(), 124);
+    /// ```
+    #[inline]
+    pub fn cl_immediate(self) -> u32 {
+        self.cs_immediate()
+    }
+    /// Return the zero-extended CS-type word offset of a `c.sw`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.sw s0,64(a3)
+    /// assert_eq!(Instruction::from_parcels([0xC2A0, 0]).0.cs_immediate(), 64);
+    /// ```
+    pub fn cs_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b011_1000) |
+        ((self.0 >> 4) & 0b000_0100) |
+        ((self.0 << 1) & 0b100_0000)
+    }
+    /// Return the sign-extended CB-type branch offset of a `c.beqz` or `c.bnez`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.beqz a3,-256
+    /// assert_eq!(Instruction::from_parcels([0xD281, 0]).0.cb_immediate(), -256i32 as u32);
+    /// // c.bnez a0,254
+    /// assert_eq!(Instruction::from_parcels([0xED7D, 0]).0.cb_immediate(), 254);
+    /// ```
+    pub fn cb_immediate(self) -> u32 {
+        ((((self.0 << 19) as i32) >> 23) as u32 & !0xFF) |
+        ((self.0 << 1)                          & 0b1100_0000) |
+        ((self.0 << 3)                          & 0b0010_0000) |
+        ((self.0 >> 7)                          & 0b0001_1000) |
+        ((self.0 >> 2)                          & 0b0000_0110)
+    }
+    /// Return the sign-extended CJ-type jump offset of a `c.j` or `c.jal`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.j 2046
+    /// assert_eq!(Instruction::from_parcels([0xAFFD, 0]).0.cj_immediate(), 2046);
+    /// // c.j -2048
+    /// assert_eq!(Instruction::from_parcels([0xB001, 0]).0.cj_immediate(), -2048i32 as u32);
+    /// ```
+    pub fn cj_immediate(self) -> u32 {
+        ((((self.0 << 19) as i32) >> 20) as u32 & !0x7FF) |
+        ((self.0 << 2)                          & 0b100_0000_0000) |
+        ((self.0 >> 1)                          & 0b011_0100_0000) |
+        ((self.0 << 1)                          & 0b000_1000_0000) |
+        ((self.0 << 3)                          & 0b000_0010_0000) |
+        ((self.0 >> 7)                          & 0b000_0001_0000) |
+        ((self.0 >> 2)                          & 0b000_0000_1110)
+    }
+
+    /// Return the sign-extended stack adjustment of a compressed `c.addi16sp`,
+    /// or [`None`] for the reserved zero immediate.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.addi16sp sp,-512
+    /// assert_eq!(Instruction::from_parcels([0x7101, 0]).0.addi16sp_immediate(), Some(-512i32 as u32));
+    /// // c.addi16sp sp,496
+    /// assert_eq!(Instruction::from_parcels([0x617D, 0]).0.addi16sp_immediate(), Some(496));
+    /// assert_eq!(Instruction::from_parcels([0x6101, 0]).0.addi16sp_immediate(), None);
+    /// ```
+    pub fn addi16sp_immediate(self) -> Option<u32> {
+        let imm = (((((self.0 << 19) as i32) >> 22) as u32) & !0x1FF) |
+            ((self.0 << 4)  & 0b01_1000_0000) |
+            ((self.0 << 1)  & 0b00_0100_0000) |
+            ((self.0 << 3)  & 0b00_0010_0000) |
+            ((self.0 >> 2)  & 0b00_0001_0000);
+        (imm != 0).then_some(imm)
+    }
+    /// Return the zero-extended stack offset of a compressed `c.addi4spn`, or
+    /// [`None`] for the reserved zero immediate.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // c.addi4spn s1,sp,1020
+    /// assert_eq!(Instruction::from_parcels([0x1FE4, 0]).0.addi4spn_immediate(), Some(1020));
+    /// // c.addi4spn a5,sp,4
+    /// assert_eq!(Instruction::from_parcels([0x005C, 0]).0.addi4spn_immediate(), Some(4));
+    /// // The all-zero parcel is the canonical illegal instruction.
+    /// assert_eq!(Instruction::from_parcels([0x0000, 0]).0.addi4spn_immediate(), None);
+    /// ```
+    pub fn addi4spn_immediate(self) -> Option<u32> {
+        let imm = self.ciw_immediate();
+        (imm != 0).then_some(imm)
+    }
+
+    /// Returns `true` if the instruction is a conditional branch.
+    ///
+    /// The category predicates only inspect the standard opcode, so compressed
+    /// instructions must be [expanded](Instruction::expand_rv32) first.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// let predicates = |i: u32| {
+    ///     let i = Instruction::new(i);
+    ///     [i.is_branch(), i.is_jump(), i.is_load(), i.is_store(), i.is_system(), i.is_op_imm(), i.is_op()]
+    /// };
+    /// // bgeu x10,x8,-4
+    /// assert_eq!(predicates(0xfe857ee3), [true, false, false, false, false, false, false]);
+    /// // jal x7,0xDEAD4
+    /// assert_eq!(predicates(0x2d5de3ef), [false, true, false, false, false, false, false]);
+    /// // jalr x31,1234(x11)
+    /// assert_eq!(predicates(0x4d258fe7), [false, true, false, false, false, false, false]);
+    /// // lw t0,8(sp)
+    /// assert_eq!(predicates(0x00812283), [false, false, true, false, false, false, false]);
+    /// // sw x27,2047(x31)
+    /// assert_eq!(predicates(0x7fbfafa3), [false, false, false, true, false, false, false]);
+    /// // csrrw a0,mstatus,a1
+    /// assert_eq!(predicates(0x30059573), [false, false, false, false, true, false, false]);
+    /// // srai x17,x7,19
+    /// assert_eq!(predicates(0x4133d893), [false, false, false, false, false, true, false]);
+    /// // sub a0,a1,a2
+    /// assert_eq!(predicates(0x40c58533), [false, false, false, false, false, false, true]);
+    /// // lui x15,0xDEAD4
+    /// assert_eq!(predicates(0xdead47b7), [false; 7]);
+    /// // c.addi a0,-1 only matches once expanded
+    /// let compressed = Instruction::from_parcels([0x157D, 0]).0;
+    /// assert!(compressed.expand_rv32().unwrap().is_op_imm());
+    /// ```
+    #[inline]
+    pub fn is_branch(self) -> bool {
+        self.opcode() == 0b1100011
+    }
+    /// Returns `true` if the instruction is an unconditional `jal` or `jalr`.
+    #[inline]
+    pub fn is_jump(self) -> bool {
+        matches!(self.opcode(), 0b1101111 | 0b1100111)
+    }
+    /// Returns `true` if the instruction is an integer load.
+    #[inline]
+    pub fn is_load(self) -> bool {
+        self.opcode() == 0b0000011
+    }
+    /// Returns `true` if the instruction is an integer store.
+    #[inline]
+    pub fn is_store(self) -> bool {
+        self.opcode() == 0b0100011
+    }
+    /// Returns `true` if the instruction has the SYSTEM opcode, used by
+    /// `ecall`, `ebreak`, the privileged instructions and Zicsr.
+    #[inline]
+    pub fn is_system(self) -> bool {
+        self.opcode() == 0b1110011
+    }
+    /// Returns `true` if the instruction is a register-immediate integer
+    /// operation.
+    #[inline]
+    pub fn is_op_imm(self) -> bool {
+        self.opcode() == 0b0010011
+    }
+    /// Returns `true` if the instruction is a register-register integer
+    /// operation.
+    #[inline]
+    pub fn is_op(self) -> bool {
+        self.opcode() == 0b0110011
+    }
+    /// Returns `true` if the instruction is an M extension multiply or divide
+    /// of the OP opcode.
+    #[inline]
+    pub fn is_muldiv(self) -> bool {
+        self.is_op() && self.funct7() == 0b0000001
+    }
+    /// Return the M extension operation of the instruction, or `None` if it is
+    /// not a multiply or divide of the OP opcode.
+    ///
+    /// The RV64 word variants of the OP-32 opcode are not included.
+    /// ```rust
+    /// use rysk::{Instruction, MulDivOp};
+    /// // mul a0,a1,a2
+    /// let mul = Instruction::new(0x02c58533);
+    /// assert!(mul.is_muldiv());
+    /// assert_eq!(mul.muldiv_op(), Some(MulDivOp::Mul));
+    /// // divu a0,a1,a2
+    /// let divu = Instruction::new(0x02c5d533);
+    /// assert!(divu.is_muldiv());
+    /// assert_eq!(divu.muldiv_op(), Some(MulDivOp::Divu));
+    /// // mulhsu a0,a1,a2
+    /// assert_eq!(Instruction::new(0x02c5a533).muldiv_op(), Some(MulDivOp::Mulhsu));
+    /// // remu a0,a1,a2
+    /// assert_eq!(Instruction::new(0x02c5f533).muldiv_op(), Some(MulDivOp::Remu));
+    /// // add a0,a1,a2
+    /// let add = Instruction::new(0x00c58533);
+    /// assert!(!add.is_muldiv());
+    /// assert_eq!(add.muldiv_op(), None);
+    /// ```
+    #[inline]
+    pub fn muldiv_op(self) -> Option<MulDivOp> {
+        if !self.is_muldiv() {
+            return None;
+        }
+        Some(match self.funct3() {
+            0b000 => MulDivOp::Mul,
+            0b001 => MulDivOp::Mulh,
+            0b010 => MulDivOp::Mulhsu,
+            0b011 => MulDivOp::Mulhu,
+            0b100 => MulDivOp::Div,
+            0b101 => MulDivOp::Divu,
+            0b110 => MulDivOp::Rem,
+            _ => MulDivOp::Remu
+        })
+    }
+
+    /// Returns `true` if the instruction is a data memory `fence`, including
+    /// `fence.tso` and `pause`.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// // fence rw,rw
+    /// let fence = Instruction::new(0x0330000f);
+    /// assert!(fence.is_fence() && !fence.is_fence_i());
+    /// // fence.i
+    /// let fence_i = Instruction::new(0x0000100f);
+    /// assert!(fence_i.is_fence_i() && !fence_i.is_fence());
+    /// // fence.tso
+    /// assert!(Instruction::new(0x8330000f).is_fence());
+    /// // addi a0,a1,4
+    /// assert!(!Instruction::new(0x00458513).is_fence());
+    /// ```
+    #[inline]
+    pub fn is_fence(self) -> bool {
+        self.opcode() == 0b0001111 && self.funct3() == 0b000
+    }
+    /// Returns `true` if the instruction is the Zifencei `fence.i`, which
+    /// synchronises the instruction and data streams.
+    #[inline]
+    pub fn is_fence_i(self) -> bool {
+        self.opcode() == 0b0001111 && self.funct3() == 0b001
+    }
+    /// Returns `true` if the instruction is the Zihintpause `pause` hint, a
+    /// `fence w,0` a spinning hart executes to yield.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// assert!(Instruction::new(0x0100000F).is_pause());
+    /// // fence rw,rw
+    /// assert!(!Instruction::new(0x0330000F).is_pause());
+    /// ```
+    #[inline]
+    pub fn is_pause(self) -> bool {
+        self.0 == 0x0100_000F
+    }
 }
 impl core::fmt::Debug for Instruction {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -260,8 +1049,112 @@ impl core::fmt::Debug for Instruction {
     }
 }
 
+/// A major opcode of the standard 32-bit instruction encoding.
+///
+/// Opcodes reserved by the base ISA, along with the low bit patterns marking
+/// instructions longer than 32 bits, have no variant.
+/// ```rust
+/// use rysk::Opcode;
+/// let mut opcodes = 0;
+/// for value in 0..=u8::MAX {
+///     if let Some(opcode) = Opcode::from_u8(value) {
+///         assert_eq!(opcode as u8, value);
+///         assert_eq!(u8::from(opcode), value);
+///         opcodes += 1;
+///     }
+/// }
+/// assert_eq!(opcodes, 26);
+/// assert_eq!(Opcode::from_u8(0b1100011), Some(Opcode::Branch));
+/// // Reserved
+/// assert_eq!(Opcode::from_u8(0b1101011), None);
+/// // 48-bit instructions
+/// assert_eq!(Opcode::from_u8(0b0011111), None);
+/// ```
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Opcode {
+    Load = 0b0000011,
+    LoadFp = 0b0000111,
+    Custom0 = 0b0001011,
+    MiscMem = 0b0001111,
+    OpImm = 0b0010011,
+    Auipc = 0b0010111,
+    OpImm32 = 0b0011011,
+    Store = 0b0100011,
+    StoreFp = 0b0100111,
+    Custom1 = 0b0101011,
+    Amo = 0b0101111,
+    Op = 0b0110011,
+    Lui = 0b0110111,
+    Op32 = 0b0111011,
+    Madd = 0b1000011,
+    Msub = 0b1000111,
+    Nmsub = 0b1001011,
+    Nmadd = 0b1001111,
+    OpFp = 0b1010011,
+    OpV = 0b1010111,
+    Custom2 = 0b1011011,
+    Branch = 0b1100011,
+    Jalr = 0b1100111,
+    Jal = 0b1101111,
+    System = 0b1110011,
+    Custom3 = 0b1111011
+}
+impl Opcode {
+    /// Return the opcode for a 7-bit opcode field, or `None` if it is
+    /// reserved or out of range.
+    pub fn from_u8(opcode: u8) -> Option<Self> {
+        Some(match opcode {
+            0b0000011 => Self::Load,
+            0b0000111 => Self::LoadFp,
+            0b0001011 => Self::Custom0,
+            0b0001111 => Self::MiscMem,
+            0b0010011 => Self::OpImm,
+            0b0010111 => Self::Auipc,
+            0b0011011 => Self::OpImm32,
+            0b0100011 => Self::Store,
+            0b0100111 => Self::StoreFp,
+            0b0101011 => Self::Custom1,
+            0b0101111 => Self::Amo,
+            0b0110011 => Self::Op,
+            0b0110111 => Self::Lui,
+            0b0111011 => Self::Op32,
+            0b1000011 => Self::Madd,
+            0b1000111 => Self::Msub,
+            0b1001011 => Self::Nmsub,
+            0b1001111 => Self::Nmadd,
+            0b1010011 => Self::OpFp,
+            0b1010111 => Self::OpV,
+            0b1011011 => Self::Custom2,
+            0b1100011 => Self::Branch,
+            0b1100111 => Self::Jalr,
+            0b1101111 => Self::Jal,
+            0b1110011 => Self::System,
+            0b1111011 => Self::Custom3,
+            _ => return None
+        })
+    }
+}
+impl From<Opcode> for u8 {
+    #[inline]
+    fn from(opcode: Opcode) -> Self {
+        opcode as u8
+    }
+}
+
+/// An integer register, ordered by register number.
+/// ```rust
+/// use rysk::Register;
+/// use std::collections::{BTreeSet, HashSet};
+/// let set: BTreeSet<_> = [Register::X10, Register::X2, Register::X31, Register::X2].into_iter().collect();
+/// assert_eq!(set.into_iter().collect::<Vec<_>>(), [Register::X2, Register::X10, Register::X31]);
+/// assert!(Register::X0 < Register::X1);
+///
+/// let set: HashSet<_> = [Register::X10, Register::X10].into_iter().collect();
+/// assert_eq!(set.len(), 1);
+/// ```
 #[repr(u8)]
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Register {
     X0 = 0,
     X1 = 1,
@@ -313,9 +1206,390 @@ impl Register {
     pub unsafe fn new_unchecked(num: u8) -> Self {
         core::mem::transmute(num)
     }
+    /// Return the standard calling convention name of the register.
+    ///
+    /// `x8` is named `s0` rather than its `fp` alias, matching GNU objdump.
+    /// ```rust
+    /// use rysk::Register;
+    /// assert_eq!(Register::X0.abi_name(), "zero");
+    /// assert_eq!(Register::X2.abi_name(), "sp");
+    /// assert_eq!(Register::X8.abi_name(), "s0");
+    /// assert_eq!(Register::X10.abi_name(), "a0");
+    /// assert_eq!(Register::X31.abi_name(), "t6");
+    /// ```
+    pub fn abi_name(self) -> &'static str {
+        Self::ABI_NAMES[self]
+    }
+    /// Parse a register from its lowercase numeric `x<N>` name or any ABI
+    /// name, including `fp` for `x8`.
+    /// ```rust
+    /// use rysk::Register;
+    /// assert_eq!(Register::from_name("x0"), Some(Register::X0));
+    /// assert_eq!(Register::from_name("x31"), Some(Register::X31));
+    /// assert_eq!(Register::from_name("zero"), Some(Register::X0));
+    /// assert_eq!(Register::from_name("ra"), Some(Register::X1));
+    /// assert_eq!(Register::from_name("sp"), Some(Register::X2));
+    /// assert_eq!(Register::from_name("gp"), Some(Register::X3));
+    /// assert_eq!(Register::from_name("tp"), Some(Register::X4));
+    /// assert_eq!(Register::from_name("s0"), Some(Register::X8));
+    /// assert_eq!(Register::from_name("fp"), Some(Register::X8));
+    /// assert_eq!(Register::from_name("s11"), Some(Register::X27));
+    /// for n in 0..32 {
+    ///     let r = Register::new(n).unwrap();
+    ///     assert_eq!(Register::from_name(r.abi_name()), Some(r));
+    ///     assert_eq!(Register::from_name(&format!("x{n}")), Some(r));
+    /// }
+    /// for name in ["", "x", "x32", "xa", "x01", "x-1", "x+1", "X1", "SP", "a8", "s12", "t7"] {
+    ///     assert_eq!(Register::from_name(name), None);
+    /// }
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name == "fp" {
+            return Some(Self::X8);
+        }
+        if let Some(i) = Self::ABI_NAMES.iter().position(|&abi| abi == name) {
+            return Self::new(i as u8);
+        }
+        let digits = name.strip_prefix('x')?;
+        // Reject empty, signed and zero-padded numbers.
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || (digits.len() > 1 && digits.starts_with('0')) {
+            return None;
+        }
+        Self::new(digits.parse().ok()?)
+    }
+    /// Return the role of the register in the integer calling convention.
+    /// ```rust
+    /// use rysk::{Register, RegisterRole};
+    /// assert_eq!(Register::X0.role(), RegisterRole::Zero);
+    /// assert_eq!(Register::X1.role(), RegisterRole::ReturnAddress);
+    /// assert_eq!(Register::X2.role(), RegisterRole::StackPointer);
+    /// assert_eq!(Register::X3.role(), RegisterRole::GlobalPointer);
+    /// assert_eq!(Register::X4.role(), RegisterRole::ThreadPointer);
+    /// assert_eq!(Register::X8.role(), RegisterRole::Saved);
+    /// assert_eq!(Register::X27.role(), RegisterRole::Saved);
+    /// assert_eq!(Register::X5.role(), RegisterRole::Temporary);
+    /// assert_eq!(Register::X31.role(), RegisterRole::Temporary);
+    /// assert_eq!(Register::X17.role(), RegisterRole::Argument);
+    /// // The role agrees with the ABI name.
+    /// for n in 0..32 {
+    ///     let r = Register::new(n).unwrap();
+    ///     let prefix = &r.abi_name()[..1];
+    ///     assert_eq!(r.role() == RegisterRole::Saved, prefix == "s" && r != Register::X2);
+    ///     assert_eq!(r.role() == RegisterRole::Temporary, prefix == "t" && r != Register::X4);
+    ///     assert_eq!(r.role() == RegisterRole::Argument, prefix == "a");
+    /// }
+    /// ```
+    pub fn role(self) -> RegisterRole {
+        match self as u8 {
+            0 => RegisterRole::Zero,
+            1 => RegisterRole::ReturnAddress,
+            2 => RegisterRole::StackPointer,
+            3 => RegisterRole::GlobalPointer,
+            4 => RegisterRole::ThreadPointer,
+            5..=7 | 28..=31 => RegisterRole::Temporary,
+            8 | 9 | 18..=27 => RegisterRole::Saved,
+            _ => RegisterRole::Argument
+        }
+    }
+    /// Returns `true` if the register is not preserved across calls, that is
+    /// `ra`, `t0`-`t6` and `a0`-`a7`.
+    /// ```rust
+    /// use rysk::Register;
+    /// assert!(Register::X5.is_caller_saved());
+    /// assert!(Register::X1.is_caller_saved());
+    /// assert!(Register::X10.is_caller_saved());
+    /// assert!(!Register::X8.is_caller_saved());
+    /// assert!(!Register::X0.is_caller_saved());
+    /// ```
+    #[inline]
+    pub fn is_caller_saved(self) -> bool {
+        matches!(self.role(), RegisterRole::ReturnAddress | RegisterRole::Temporary | RegisterRole::Argument)
+    }
+    /// Returns `true` if the register is preserved across calls, that is `sp`
+    /// and `s0`-`s11`.
+    ///
+    /// `zero`, `gp` and `tp` are neither caller- nor callee-saved.
+    /// ```rust
+    /// use rysk::Register;
+    /// assert!(Register::X8.is_callee_saved());
+    /// assert!(Register::X2.is_callee_saved());
+    /// assert!(!Register::X5.is_callee_saved());
+    /// assert!(!Register::X3.is_callee_saved() && !Register::X3.is_caller_saved());
+    /// assert!(!Register::X4.is_callee_saved() && !Register::X4.is_caller_saved());
+    /// ```
+    #[inline]
+    pub fn is_callee_saved(self) -> bool {
+        matches!(self.role(), RegisterRole::StackPointer | RegisterRole::Saved)
+    }
+    /// Returns `true` if the register is one of the argument registers
+    /// `a0`-`a7`, of which `a0` and `a1` also hold return values.
+    /// ```rust
+    /// use rysk::Register;
+    /// assert!(Register::X10.is_argument());
+    /// assert!(Register::X17.is_argument());
+    /// assert!(!Register::X18.is_argument());
+    /// ```
+    #[inline]
+    pub fn is_argument(self) -> bool {
+        self.role() == RegisterRole::Argument
+    }
+
+    const ABI_NAMES: [&'static str; 32] = [
+        "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2",
+        "s0", "s1", "a0", "a1", "a2", "a3", "a4", "a5",
+        "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7",
+        "s8", "s9", "s10", "s11", "t3", "t4", "t5", "t6"
+    ];
 }
+/// Formats the register number, with the alternate form `{:#?}` also
+/// including the ABI name.
+/// ```rust
+/// use rysk::Register;
+/// assert_eq!(format!("{:?}", Register::X2), "x2");
+/// assert_eq!(format!("{:#?}", Register::X2), "x2 (sp)");
+/// ```
 impl core::fmt::Debug for Register {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "x{}", *self as u8)
+        if f.alternate() {
+            write!(f, "x{} ({})", *self as u8, self.abi_name())
+        } else {
+            write!(f, "x{}", *self as u8)
+        }
+    }
+}
+/// Formats the ABI name of the register.
+/// ```rust
+/// use rysk::Register;
+/// assert_eq!(Register::X1.to_string(), "ra");
+/// ```
+impl core::fmt::Display for Register {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.abi_name())
+    }
+}
+/// Index a register file by [`Register`] without bounds checks, as a register
+/// number can never exceed 31.
+/// ```rust
+/// use rysk::Register;
+/// let mut regs = [0u32; 32];
+/// regs[Register::X10] = 42;
+/// assert_eq!(regs[Register::X10], 42);
+/// assert_eq!(regs[10], 42);
+/// ```
+impl<T> core::ops::Index<Register> for [T; 32] {
+    type Output = T;
+    #[inline]
+    fn index(&self, r: Register) -> &T {
+        // Safety: The register number cannot exceed 31.
+        unsafe { self.get_unchecked(r as usize) }
+    }
+}
+impl<T> core::ops::IndexMut<Register> for [T; 32] {
+    #[inline]
+    fn index_mut(&mut self, r: Register) -> &mut T {
+        // Safety: The register number cannot exceed 31.
+        unsafe { self.get_unchecked_mut(r as usize) }
+    }
+}
+/// Convert a register number to a [`Register`], returning the number as the
+/// error if it exceeds 31.
+/// ```rust
+/// use rysk::Register;
+/// assert_eq!(Register::try_from(31), Ok(Register::X31));
+/// assert_eq!(Register::try_from(32), Err(32));
+/// assert_eq!(u8::from(Register::X31), 31);
+/// ```
+impl TryFrom<u8> for Register {
+    type Error = u8;
+    #[inline]
+    fn try_from(num: u8) -> Result<Self, Self::Error> {
+        Self::new(num).ok_or(num)
+    }
+}
+impl From<Register> for u8 {
+    #[inline]
+    fn from(r: Register) -> Self {
+        r as u8
+    }
+}
+
+/// The role of an integer register in the standard calling convention, as
+/// returned by [`Register::role`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RegisterRole {
+    /// `zero`, hardwired to zero.
+    Zero,
+    /// `ra`, the return address.
+    ReturnAddress,
+    /// `sp`, the stack pointer.
+    StackPointer,
+    /// `gp`, the global pointer.
+    GlobalPointer,
+    /// `tp`, the thread pointer.
+    ThreadPointer,
+    /// `s0`-`s11`, saved by the callee. `s0` is also the frame pointer.
+    Saved,
+    /// `t0`-`t6`, temporaries saved by the caller.
+    Temporary,
+    /// `a0`-`a7`, function arguments and return values.
+    Argument
+}
+
+/// A floating-point register of the F and D extensions, ordered by register
+/// number.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FRegister {
+    F0 = 0,
+    F1 = 1,
+    F2 = 2,
+    F3 = 3,
+    F4 = 4,
+    F5 = 5,
+    F6 = 6,
+    F7 = 7,
+    F8 = 8,
+    F9 = 9,
+    F10 = 10,
+    F11 = 11,
+    F12 = 12,
+    F13 = 13,
+    F14 = 14,
+    F15 = 15,
+    F16 = 16,
+    F17 = 17,
+    F18 = 18,
+    F19 = 19,
+    F20 = 20,
+    F21 = 21,
+    F22 = 22,
+    F23 = 23,
+    F24 = 24,
+    F25 = 25,
+    F26 = 26,
+    F27 = 27,
+    F28 = 28,
+    F29 = 29,
+    F30 = 30,
+    F31 = 31
+}
+impl FRegister {
+    /// Convert the register number to the register or [`None`] if `num > 31`.
+    #[inline]
+    pub fn new(num: u8) -> Option<Self> {
+        if num > 31 {
+            None
+        } else {
+            Some(unsafe { Self::new_unchecked(num) })
+        }
+    }
+    /// Convert the register number to the register.
+    /// # Safety
+    /// It is undefined behaviour for `num` to be greater than 31.
+    #[inline(always)]
+    pub unsafe fn new_unchecked(num: u8) -> Self {
+        core::mem::transmute(num)
+    }
+    /// Return the standard calling convention name of the register.
+    /// ```rust
+    /// use rysk::FRegister;
+    /// assert_eq!(FRegister::F0.abi_name(), "ft0");
+    /// assert_eq!(FRegister::F8.abi_name(), "fs0");
+    /// assert_eq!(FRegister::F10.abi_name(), "fa0");
+    /// assert_eq!(FRegister::F27.abi_name(), "fs11");
+    /// assert_eq!(FRegister::F31.abi_name(), "ft11");
+    /// ```
+    pub fn abi_name(self) -> &'static str {
+        Self::ABI_NAMES[self]
+    }
+    /// Parse a register from its lowercase numeric `f<N>` name or ABI name.
+    /// ```rust
+    /// use rysk::FRegister;
+    /// assert_eq!(FRegister::from_name("f31"), Some(FRegister::F31));
+    /// assert_eq!(FRegister::from_name("fa1"), Some(FRegister::F11));
+    /// for n in 0..32 {
+    ///     let r = FRegister::new(n).unwrap();
+    ///     assert_eq!(FRegister::from_name(r.abi_name()), Some(r));
+    ///     assert_eq!(FRegister::from_name(&format!("f{n}")), Some(r));
+    /// }
+    /// for name in ["", "f", "f32", "f01", "f+1", "x1", "fa8", "ft12"] {
+    ///     assert_eq!(FRegister::from_name(name), None);
+    /// }
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        if let Some(i) = Self::ABI_NAMES.iter().position(|&abi| abi == name) {
+            return Self::new(i as u8);
+        }
+        let digits = name.strip_prefix('f')?;
+        // Reject empty, signed and zero-padded numbers.
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) || (digits.len() > 1 && digits.starts_with('0')) {
+            return None;
+        }
+        Self::new(digits.parse().ok()?)
+    }
+
+    const ABI_NAMES: [&'static str; 32] = [
+        "ft0", "ft1", "ft2", "ft3", "ft4", "ft5", "ft6", "ft7",
+        "fs0", "fs1", "fa0", "fa1", "fa2", "fa3", "fa4", "fa5",
+        "fa6", "fa7", "fs2", "fs3", "fs4", "fs5", "fs6", "fs7",
+        "fs8", "fs9", "fs10", "fs11", "ft8", "ft9", "ft10", "ft11"
+    ];
+}
+/// Formats the register number, with the alternate form `{:#?}` also
+/// including the ABI name.
+/// ```rust
+/// use rysk::FRegister;
+/// assert_eq!(format!("{:?}", FRegister::F10), "f10");
+/// assert_eq!(format!("{:#?}", FRegister::F10), "f10 (fa0)");
+/// ```
+impl core::fmt::Debug for FRegister {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if f.alternate() {
+            write!(f, "f{} ({})", *self as u8, self.abi_name())
+        } else {
+            write!(f, "f{}", *self as u8)
+        }
+    }
+}
+/// Formats the ABI name of the register.
+impl core::fmt::Display for FRegister {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.pad(self.abi_name())
+    }
+}
+impl<T> core::ops::Index<FRegister> for [T; 32] {
+    type Output = T;
+    #[inline]
+    fn index(&self, r: FRegister) -> &T {
+        // Safety: The register number cannot exceed 31.
+        unsafe { self.get_unchecked(r as usize) }
+    }
+}
+impl<T> core::ops::IndexMut<FRegister> for [T; 32] {
+    #[inline]
+    fn index_mut(&mut self, r: FRegister) -> &mut T {
+        // Safety: The register number cannot exceed 31.
+        unsafe { self.get_unchecked_mut(r as usize) }
+    }
+}
+/// Convert a register number to a [`FRegister`], returning the number as the
+/// error if it exceeds 31.
+/// ```rust
+/// use rysk::FRegister;
+/// assert_eq!(FRegister::try_from(31), Ok(FRegister::F31));
+/// assert_eq!(FRegister::try_from(32), Err(32));
+/// assert_eq!(u8::from(FRegister::F31), 31);
+/// ```
+impl TryFrom<u8> for FRegister {
+    type Error = u8;
+    #[inline]
+    fn try_from(num: u8) -> Result<Self, Self::Error> {
+        Self::new(num).ok_or(num)
+    }
+}
+impl From<FRegister> for u8 {
+    #[inline]
+    fn from(r: FRegister) -> Self {
+        r as u8
     }
 }
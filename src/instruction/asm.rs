@@ -0,0 +1,255 @@
+use core::fmt::{self, Write};
+
+use super::{AluOp, BranchKind, Decoded, Instruction, LoadWidth, Register, StoreWidth};
+
+impl Instruction {
+    /// Return a formatter writing the instruction as GNU-style RV32I assembly.
+    ///
+    /// Registers use their ABI names and immediates are decimal, except for the
+    /// upper immediate of `lui` and `auipc` which is hexadecimal as printed by
+    /// GNU objdump. Branch and jump targets are byte offsets from the
+    /// instruction as no address is known. Compressed instructions are printed
+    /// as their expansion. Anything else is printed as a `.word` or `.half`
+    /// directive.
+    ///
+    /// The common pseudo-instructions `nop`, `ret`, `mv`, `li`, `j`, `not` and
+    /// `neg` are printed in place of the instructions they stand for, use
+    /// [`Instruction::display_asm_canonical`] to always print the base form.
+    /// The alternate form appends the raw instruction as a comment.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// let asm = |i: u32| Instruction::new(i).display_asm().to_string();
+    /// // R-type
+    /// assert_eq!(asm(0x40c58533), "sub a0,a1,a2");
+    /// assert_eq!(asm(0x00c5b533), "sltu a0,a1,a2");
+    /// // I-type
+    /// assert_eq!(asm(0x00458513), "addi a0,a1,4");
+    /// assert_eq!(asm(0x4133d493), "srai s1,t2,19");
+    /// assert_eq!(asm(0xfff4c503), "lbu a0,-1(s1)");
+    /// assert_eq!(asm(0x000580e7), "jalr ra,0(a1)");
+    /// // S-type
+    /// assert_eq!(asm(0xfe812c23), "sw s0,-8(sp)");
+    /// // B-type
+    /// assert_eq!(asm(0xfeb50ce3), "beq a0,a1,-8");
+    /// // U-type
+    /// assert_eq!(asm(0xdead47b7), "lui a5,0xdead4");
+    /// assert_eq!(asm(0x00001297), "auipc t0,0x1");
+    /// // J-type
+    /// assert_eq!(asm(0x001000ef), "jal ra,2048");
+    ///
+    /// assert_eq!(asm(0x0310000f), "fence rw,w");
+    /// assert_eq!(asm(0x8330000f), "fence.tso");
+    /// assert_eq!(asm(0x8220000f), "fence r,r");
+    /// assert_eq!(asm(0x00000073), "ecall");
+    /// assert_eq!(asm(0x00100073), "ebreak");
+    /// assert_eq!(asm(0xffffffff), ".word 0xffffffff");
+    ///
+    /// // c.addi a0,-1
+    /// assert_eq!(Instruction::from_parcels([0x157D, 0]).0.display_asm().to_string(), "addi a0,a0,-1");
+    /// assert_eq!(Instruction::from_parcels([0x0000, 0]).0.display_asm().to_string(), ".half 0x0000");
+    ///
+    /// // Pseudo-instructions
+    /// assert_eq!(asm(0x00000013), "nop");
+    /// assert_eq!(asm(0x00008067), "ret");
+    /// assert_eq!(asm(0x00058513), "mv a0,a1");
+    /// assert_eq!(asm(0xffb00513), "li a0,-5");
+    /// assert_eq!(asm(0xff9ff06f), "j -8");
+    /// assert_eq!(asm(0xfff5c513), "not a0,a1");
+    /// assert_eq!(asm(0x40b00533), "neg a0,a1");
+    /// // c.li a0,-5
+    /// assert_eq!(Instruction::from_parcels([0x556D, 0]).0.display_asm().to_string(), "li a0,-5");
+    ///
+    /// // Alternate form
+    /// assert_eq!(format!("{}", Instruction::new(0x00458513).display_asm()), "addi a0,a1,4");
+    /// assert_eq!(format!("{:#}", Instruction::new(0x00458513).display_asm()), "addi a0,a1,4    # 0x00458513");
+    /// assert_eq!(format!("{:#}", Instruction::new(0x00000013).display_asm_canonical()), "addi zero,zero,0    # 0x00000013");
+    /// assert_eq!(format!("{:#}", Instruction::from_parcels([0x157D, 0]).0.display_asm()), "addi a0,a0,-1    # 0x157d");
+    /// ```
+    pub fn display_asm(self) -> impl fmt::Display {
+        Asm { instruction: self, pseudo: true }
+    }
+    /// Write the instruction as by [`Instruction::display_asm`] to `w`,
+    /// without allocating.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// let mut buf = String::new();
+    /// // addi a0,a1,4
+    /// Instruction::new(0x00458513).write_asm(&mut buf).unwrap();
+    /// buf.push('\n');
+    /// // ret
+    /// Instruction::new(0x00008067).write_asm(&mut buf).unwrap();
+    /// assert_eq!(buf, "addi a0,a1,4\nret");
+    /// ```
+    pub fn write_asm<W: Write>(self, w: &mut W) -> fmt::Result {
+        write!(w, "{}", self.display_asm())
+    }
+    /// Return a formatter writing the instruction as GNU-style RV32I assembly
+    /// without substituting pseudo-instructions.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// let asm = |i: u32| Instruction::new(i).display_asm_canonical().to_string();
+    /// assert_eq!(asm(0x00000013), "addi zero,zero,0");
+    /// assert_eq!(asm(0x00008067), "jalr zero,0(ra)");
+    /// assert_eq!(asm(0x00058513), "addi a0,a1,0");
+    /// assert_eq!(asm(0xffb00513), "addi a0,zero,-5");
+    /// assert_eq!(asm(0xff9ff06f), "jal zero,-8");
+    /// assert_eq!(asm(0xfff5c513), "xori a0,a1,-1");
+    /// assert_eq!(asm(0x40b00533), "sub a0,zero,a1");
+    /// ```
+    pub fn display_asm_canonical(self) -> impl fmt::Display {
+        Asm { instruction: self, pseudo: false }
+    }
+}
+
+struct Asm {
+    instruction: Instruction,
+    /// Print pseudo-instructions where one applies.
+    pseudo: bool
+}
+impl Asm {
+    /// Write the pseudo-instruction standing for `decoded`, returning `None`
+    /// if there is none.
+    fn write_pseudo(f: &mut fmt::Formatter<'_>, decoded: Decoded) -> Option<fmt::Result> {
+        Some(match decoded {
+            Decoded::OpImm { rd: Register::X0, rs1: Register::X0, imm: 0, op: AluOp::Add } => f.write_str("nop"),
+            Decoded::OpImm { rd, rs1: Register::X0, imm, op: AluOp::Add } => write!(f, "li {rd},{}", imm as i32),
+            Decoded::OpImm { rd, rs1, imm: 0, op: AluOp::Add } => write!(f, "mv {rd},{rs1}"),
+            Decoded::OpImm { rd, rs1, imm: u32::MAX, op: AluOp::Xor } => write!(f, "not {rd},{rs1}"),
+            Decoded::Op { rd, rs1: Register::X0, rs2, op: AluOp::Sub } => write!(f, "neg {rd},{rs2}"),
+            Decoded::Jal { rd: Register::X0, imm } => write!(f, "j {}", imm as i32),
+            Decoded::Jalr { rd: Register::X0, rs1: Register::X1, imm: 0 } => f.write_str("ret"),
+            _ => return None
+        })
+    }
+}
+impl fmt::Display for Asm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_instruction(f)?;
+        if f.alternate() {
+            let i = self.instruction;
+            if i.compressed() {
+                write!(f, "    # {:#06x}", i.0)?;
+            } else {
+                write!(f, "    # {:#010x}", i.0)?;
+            }
+        }
+        Ok(())
+    }
+}
+impl Asm {
+    fn write_instruction(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let i = self.instruction;
+        let decoded = i.expand_rv32().map_or(Decoded::Unknown, Instruction::decode);
+        if self.pseudo {
+            if let Some(result) = Self::write_pseudo(f, decoded) {
+                return result;
+            }
+        }
+        match decoded {
+            Decoded::Lui { rd, imm } => write!(f, "lui {rd},{:#x}", imm >> 12),
+            Decoded::Auipc { rd, imm } => write!(f, "auipc {rd},{:#x}", imm >> 12),
+            Decoded::Jal { rd, imm } => write!(f, "jal {rd},{}", imm as i32),
+            Decoded::Jalr { rd, rs1, imm } => write!(f, "jalr {rd},{}({rs1})", imm as i32),
+            Decoded::Branch { rs1, rs2, imm, kind } => write!(f, "{} {rs1},{rs2},{}", kind.mnemonic(), imm as i32),
+            Decoded::Load { rd, rs1, imm, width } => write!(f, "{} {rd},{}({rs1})", width.mnemonic(), imm as i32),
+            Decoded::Store { rs1, rs2, imm, width } => write!(f, "{} {rs2},{}({rs1})", width.mnemonic(), imm as i32),
+            Decoded::OpImm { rd, rs1, imm, op } => match op.immediate_mnemonic() {
+                Some(mnemonic) => write!(f, "{mnemonic} {rd},{rs1},{}", imm as i32),
+                None => write!(f, ".word {:#010x}", i.0)
+            },
+            Decoded::Op { rd, rs1, rs2, op } => write!(f, "{} {rd},{rs1},{rs2}", op.mnemonic()),
+            // Reserved fence modes, including TSO with any other sets, are
+            // printed as the plain fence they execute as.
+            Decoded::Fence { pred: 0b0011, succ: 0b0011 } if i.fence_fm() == 0b1000 => f.write_str("fence.tso"),
+            Decoded::Fence { pred, succ } => {
+                f.write_str("fence ")?;
+                write_fence_set(f, pred)?;
+                f.write_char(',')?;
+                write_fence_set(f, succ)
+            },
+            Decoded::Ecall => f.write_str("ecall"),
+            Decoded::Ebreak => f.write_str("ebreak"),
+            Decoded::Unknown if i.compressed() => write!(f, ".half {:#06x}", i.0),
+            Decoded::Unknown => write!(f, ".word {:#010x}", i.0)
+        }
+    }
+}
+
+/// Write the `iorw` letters of a fence predecessor or successor set, or `0`
+/// for the empty set.
+fn write_fence_set(w: &mut impl Write, set: u8) -> fmt::Result {
+    if set == 0 {
+        return w.write_char('0');
+    }
+    for (bit, c) in [(0b1000, 'i'), (0b0100, 'o'), (0b0010, 'r'), (0b0001, 'w')] {
+        if set & bit != 0 {
+            w.write_char(c)?;
+        }
+    }
+    Ok(())
+}
+
+impl BranchKind {
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Eq => "beq",
+            Self::Ne => "bne",
+            Self::Lt => "blt",
+            Self::Ge => "bge",
+            Self::Ltu => "bltu",
+            Self::Geu => "bgeu"
+        }
+    }
+}
+impl LoadWidth {
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Byte => "lb",
+            Self::Half => "lh",
+            Self::Word => "lw",
+            Self::ByteUnsigned => "lbu",
+            Self::HalfUnsigned => "lhu"
+        }
+    }
+}
+impl StoreWidth {
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Byte => "sb",
+            Self::Half => "sh",
+            Self::Word => "sw"
+        }
+    }
+}
+impl AluOp {
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Self::Add => "add",
+            Self::Sub => "sub",
+            Self::Sll => "sll",
+            Self::Slt => "slt",
+            Self::Sltu => "sltu",
+            Self::Xor => "xor",
+            Self::Srl => "srl",
+            Self::Sra => "sra",
+            Self::Or => "or",
+            Self::And => "and"
+        }
+    }
+    /// The mnemonic of the OP-IMM form, or [`None`] for `Sub`, which has no
+    /// immediate form.
+    fn immediate_mnemonic(self) -> Option<&'static str> {
+        match self {
+            Self::Add => Some("addi"),
+            Self::Sub => None,
+            Self::Sll => Some("slli"),
+            Self::Slt => Some("slti"),
+            Self::Sltu => Some("sltiu"),
+            Self::Xor => Some("xori"),
+            Self::Srl => Some("srli"),
+            Self::Sra => Some("srai"),
+            Self::Or => Some("ori"),
+            Self::And => Some("andi")
+        }
+    }
+}
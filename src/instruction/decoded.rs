@@ -0,0 +1,367 @@
+use super::{Instruction, Register};
+
+/// The comparison performed by a conditional branch.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BranchKind {
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+    Ltu,
+    Geu
+}
+/// The width and extension of a load.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoadWidth {
+    Byte,
+    Half,
+    Word,
+    ByteUnsigned,
+    HalfUnsigned
+}
+/// The width of a store.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StoreWidth {
+    Byte,
+    Half,
+    Word
+}
+/// An integer ALU operation of the OP and OP-IMM opcodes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AluOp {
+    Add,
+    /// Only encodable by register-register OP instructions.
+    Sub,
+    Sll,
+    Slt,
+    Sltu,
+    Xor,
+    Srl,
+    Sra,
+    Or,
+    And
+}
+/// A multiply or divide operation of the M extension.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MulDivOp {
+    Mul,
+    /// Upper half of the signed product.
+    Mulh,
+    /// Upper half of the signed by unsigned product.
+    Mulhsu,
+    /// Upper half of the unsigned product.
+    Mulhu,
+    Div,
+    Divu,
+    Rem,
+    Remu
+}
+/// The read-modify-write operation of a Zicsr instruction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CsrOp {
+    /// `csrrw` and `csrrwi`
+    ReadWrite,
+    /// `csrrs` and `csrrsi`
+    ReadSet,
+    /// `csrrc` and `csrrci`
+    ReadClear
+}
+/// The source operand of a Zicsr instruction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CsrSource {
+    Register(Register),
+    /// The 5-bit zero-extended immediate of the `i` variants.
+    Immediate(u8)
+}
+/// An instruction of the SYSTEM opcode.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SystemOp {
+    Ecall,
+    Ebreak,
+    Mret,
+    Sret,
+    Wfi,
+    Csr { rd: Register, csr: u16, op: CsrOp, source: CsrSource }
+}
+/// The memory ordering of an atomic instruction, given by its `aq` and `rl` bits.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AmoOrdering {
+    Relaxed,
+    Acquire,
+    Release,
+    AcquireRelease
+}
+impl AmoOrdering {
+    /// Return the ordering for the acquire and release bits.
+    /// ```rust
+    /// use rysk::AmoOrdering;
+    /// assert_eq!(AmoOrdering::from_bits(false, false), AmoOrdering::Relaxed);
+    /// assert_eq!(AmoOrdering::from_bits(false, true), AmoOrdering::Release);
+    /// ```
+    #[inline]
+    pub fn from_bits(aq: bool, rl: bool) -> Self {
+        match (aq, rl) {
+            (false, false) => Self::Relaxed,
+            (true, false) => Self::Acquire,
+            (false, true) => Self::Release,
+            (true, true) => Self::AcquireRelease
+        }
+    }
+}
+/// The read-modify-write operation of an atomic memory operation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AmoOp {
+    Swap,
+    Add,
+    And,
+    Or,
+    Xor,
+    /// Signed minimum.
+    Min,
+    /// Signed maximum.
+    Max,
+    /// Unsigned minimum.
+    Minu,
+    /// Unsigned maximum.
+    Maxu
+}
+impl AmoOp {
+    /// Return the operation of an AMO instruction's funct5, or `None` for
+    /// `lr`, `sc` and unknown encodings.
+    /// ```rust
+    /// use rysk::{AmoOp, Instruction};
+    /// // amoadd.w.aqrl a0,a1,(a2)
+    /// assert_eq!(AmoOp::from_funct5(Instruction::new(0x06b6252f).amo_funct5()), Some(AmoOp::Add));
+    /// // lr.w.aq t0,(a0)
+    /// assert_eq!(AmoOp::from_funct5(Instruction::new(0x140522af).amo_funct5()), None);
+    /// ```
+    #[inline]
+    pub fn from_funct5(funct5: u8) -> Option<Self> {
+        Some(match funct5 {
+            0b00001 => Self::Swap,
+            0b00000 => Self::Add,
+            0b01100 => Self::And,
+            0b01000 => Self::Or,
+            0b00100 => Self::Xor,
+            0b10000 => Self::Min,
+            0b10100 => Self::Max,
+            0b11000 => Self::Minu,
+            0b11100 => Self::Maxu,
+            _ => return None
+        })
+    }
+}
+/// The rounding mode of a floating-point instruction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoundingMode {
+    /// Round to nearest, ties to even.
+    Rne,
+    /// Round towards zero.
+    Rtz,
+    /// Round down, towards negative infinity.
+    Rdn,
+    /// Round up, towards positive infinity.
+    Rup,
+    /// Round to nearest, ties to max magnitude.
+    Rmm,
+    /// Use the dynamic rounding mode in `fcsr`.
+    Dynamic,
+    /// A reserved rounding mode encoding.
+    Reserved(u8)
+}
+
+/// A classified RV32I base instruction.
+///
+/// Immediates are sign-extended as returned by the [`Instruction`] accessors.
+/// Shift-immediate instructions carry the shift amount in `imm`. Compressed
+/// instructions are not classified and decode as [`Decoded::Unknown`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Decoded {
+    Lui { rd: Register, imm: u32 },
+    Auipc { rd: Register, imm: u32 },
+    Jal { rd: Register, imm: u32 },
+    Jalr { rd: Register, rs1: Register, imm: u32 },
+    Branch { rs1: Register, rs2: Register, imm: u32, kind: BranchKind },
+    Load { rd: Register, rs1: Register, imm: u32, width: LoadWidth },
+    Store { rs1: Register, rs2: Register, imm: u32, width: StoreWidth },
+    OpImm { rd: Register, rs1: Register, imm: u32, op: AluOp },
+    Op { rd: Register, rs1: Register, rs2: Register, op: AluOp },
+    Fence { pred: u8, succ: u8 },
+    Ecall,
+    Ebreak,
+    /// An instruction outside the RV32I base set or a reserved encoding.
+    Unknown
+}
+
+impl Instruction {
+    /// Classify the instruction as an RV32I base instruction.
+    /// ```rust
+    /// use rysk::{AluOp, BranchKind, Decoded, Instruction, Register, StoreWidth};
+    /// // srai x17,x7,19
+    /// assert_eq!(Instruction::new(0x4133d893).decode(), Decoded::OpImm {
+    ///     rd: Register::X17, rs1: Register::X7, imm: 19, op: AluOp::Sra
+    /// });
+    /// // jalr x31,1234(x11)
+    /// assert_eq!(Instruction::new(0x4d258fe7).decode(), Decoded::Jalr {
+    ///     rd: Register::X31, rs1: Register::X11, imm: 1234
+    /// });
+    /// // sw x27,2047(x31)
+    /// assert_eq!(Instruction::new(0x7fbfafa3).decode(), Decoded::Store {
+    ///     rs1: Register::X31, rs2: Register::X27, imm: 2047, width: StoreWidth::Word
+    /// });
+    /// // bgeu x10,x8,-4
+    /// assert_eq!(Instruction::new(0xfe857ee3).decode(), Decoded::Branch {
+    ///     rs1: Register::X10, rs2: Register::X8, imm: -4i32 as u32, kind: BranchKind::Geu
+    /// });
+    /// // lui x15,0xDEAD4
+    /// assert_eq!(Instruction::new(0xdead47b7).decode(), Decoded::Lui {
+    ///     rd: Register::X15, imm: 0xDEAD_4000
+    /// });
+    /// // jal x7,0xDEAD4
+    /// assert_eq!(Instruction::new(0x2d5de3ef).decode(), Decoded::Jal {
+    ///     rd: Register::X7, imm: 0xDEAD4
+    /// });
+    /// // sub a0,a1,a2
+    /// assert_eq!(Instruction::new(0x40c58533).decode(), Decoded::Op {
+    ///     rd: Register::X10, rs1: Register::X11, rs2: Register::X12, op: AluOp::Sub
+    /// });
+    /// assert_eq!(Instruction::new(0x00000073).decode(), Decoded::Ecall);
+    /// assert_eq!(Instruction::new(0x00100073).decode(), Decoded::Ebreak);
+    /// assert_eq!(Instruction::new(0x00000000).decode(), Decoded::Unknown);
+    /// ```
+    pub fn decode(self) -> Decoded {
+        let (rd, rs1, rs2) = (self.rd(), self.rs1(), self.rs2());
+        match self.opcode() {
+            0b0110111 => Decoded::Lui { rd, imm: self.u_immediate() },
+            0b0010111 => Decoded::Auipc { rd, imm: self.u_immediate() },
+            0b1101111 => Decoded::Jal { rd, imm: self.j_immediate() },
+            0b1100111 if self.funct3() == 0b000 => Decoded::Jalr { rd, rs1, imm: self.i_immediate() },
+            0b1100011 => {
+                let kind = match self.funct3() {
+                    0b000 => BranchKind::Eq,
+                    0b001 => BranchKind::Ne,
+                    0b100 => BranchKind::Lt,
+                    0b101 => BranchKind::Ge,
+                    0b110 => BranchKind::Ltu,
+                    0b111 => BranchKind::Geu,
+                    _ => return Decoded::Unknown
+                };
+                Decoded::Branch { rs1, rs2, imm: self.b_immediate(), kind }
+            },
+            0b0000011 => {
+                let width = match self.funct3() {
+                    0b000 => LoadWidth::Byte,
+                    0b001 => LoadWidth::Half,
+                    0b010 => LoadWidth::Word,
+                    0b100 => LoadWidth::ByteUnsigned,
+                    0b101 => LoadWidth::HalfUnsigned,
+                    _ => return Decoded::Unknown
+                };
+                Decoded::Load { rd, rs1, imm: self.i_immediate(), width }
+            },
+            0b0100011 => {
+                let width = match self.funct3() {
+                    0b000 => StoreWidth::Byte,
+                    0b001 => StoreWidth::Half,
+                    0b010 => StoreWidth::Word,
+                    _ => return Decoded::Unknown
+                };
+                Decoded::Store { rs1, rs2, imm: self.s_immediate(), width }
+            },
+            0b0010011 => {
+                let shamt = self.i_immediate() & 0b1_1111;
+                let (op, imm) = match (self.funct3(), self.funct7()) {
+                    (0b000, _) => (AluOp::Add, self.i_immediate()),
+                    (0b010, _) => (AluOp::Slt, self.i_immediate()),
+                    (0b011, _) => (AluOp::Sltu, self.i_immediate()),
+                    (0b100, _) => (AluOp::Xor, self.i_immediate()),
+                    (0b110, _) => (AluOp::Or, self.i_immediate()),
+                    (0b111, _) => (AluOp::And, self.i_immediate()),
+                    (0b001, 0b0000000) => (AluOp::Sll, shamt),
+                    (0b101, 0b0000000) => (AluOp::Srl, shamt),
+                    (0b101, 0b0100000) => (AluOp::Sra, shamt),
+                    _ => return Decoded::Unknown
+                };
+                Decoded::OpImm { rd, rs1, imm, op }
+            },
+            0b0110011 => {
+                let op = match (self.funct3(), self.funct7()) {
+                    (0b000, 0b0000000) => AluOp::Add,
+                    (0b000, 0b0100000) => AluOp::Sub,
+                    (0b001, 0b0000000) => AluOp::Sll,
+                    (0b010, 0b0000000) => AluOp::Slt,
+                    (0b011, 0b0000000) => AluOp::Sltu,
+                    (0b100, 0b0000000) => AluOp::Xor,
+                    (0b101, 0b0000000) => AluOp::Srl,
+                    (0b101, 0b0100000) => AluOp::Sra,
+                    (0b110, 0b0000000) => AluOp::Or,
+                    (0b111, 0b0000000) => AluOp::And,
+                    _ => return Decoded::Unknown
+                };
+                Decoded::Op { rd, rs1, rs2, op }
+            },
+            0b0001111 if self.funct3() == 0b000 => Decoded::Fence {
+                pred: self.fence_pred(),
+                succ: self.fence_succ()
+            },
+            0b1110011 => match self.0 {
+                0x0000_0073 => Decoded::Ecall,
+                0x0010_0073 => Decoded::Ebreak,
+                _ => Decoded::Unknown
+            },
+            _ => Decoded::Unknown
+        }
+    }
+
+    /// Classify an instruction of the SYSTEM opcode, returning `None` for other
+    /// opcodes and unknown encodings.
+    ///
+    /// The instructions of funct3 `0b000` are identified by their funct12
+    /// field, with `rd` and `rs1` required to be zero.
+    /// ```rust
+    /// use rysk::{CsrOp, CsrSource, Instruction, Register, SystemOp};
+    /// let system = |i: u32| Instruction::new(i).system_op();
+    /// assert_eq!(system(0x00000073), Some(SystemOp::Ecall));
+    /// assert_eq!(system(0x00100073), Some(SystemOp::Ebreak));
+    /// assert_eq!(system(0x30200073), Some(SystemOp::Mret));
+    /// assert_eq!(system(0x10200073), Some(SystemOp::Sret));
+    /// assert_eq!(system(0x10500073), Some(SystemOp::Wfi));
+    /// // csrrs a0,mstatus,a1
+    /// assert_eq!(system(0x3005a573), Some(SystemOp::Csr {
+    ///     rd: Register::X10, csr: 0x300, op: CsrOp::ReadSet, source: CsrSource::Register(Register::X11)
+    /// }));
+    /// // csrrci zero,mip,8
+    /// assert_eq!(system(0x34447073), Some(SystemOp::Csr {
+    ///     rd: Register::X0, csr: 0x344, op: CsrOp::ReadClear, source: CsrSource::Immediate(8)
+    /// }));
+    /// // wfi with a non-zero rd
+    /// assert_eq!(system(0x10500573), None);
+    /// // addi a0,a1,4
+    /// assert_eq!(system(0x00458513), None);
+    /// ```
+    pub fn system_op(self) -> Option<SystemOp> {
+        if self.opcode() != 0b1110011 {
+            return None;
+        }
+        let op = match self.funct3() {
+            0b000 => return Some(match self.0 {
+                0x0000_0073 => SystemOp::Ecall,
+                0x0010_0073 => SystemOp::Ebreak,
+                0x3020_0073 => SystemOp::Mret,
+                0x1020_0073 => SystemOp::Sret,
+                0x1050_0073 => SystemOp::Wfi,
+                _ => return None
+            }),
+            0b001 | 0b101 => CsrOp::ReadWrite,
+            0b010 | 0b110 => CsrOp::ReadSet,
+            0b011 | 0b111 => CsrOp::ReadClear,
+            _ => return None
+        };
+        let source = if self.funct3() & 0b100 != 0 {
+            CsrSource::Immediate(self.csr_zimm())
+        } else {
+            CsrSource::Register(self.rs1())
+        };
+        Some(SystemOp::Csr { rd: self.rd(), csr: self.csr(), op, source })
+    }
+}
@@ -0,0 +1,590 @@
+//! Instruction encoders.
+//!
+//! Decoding an encoded instruction recovers every operand, up to the masking
+//! of opcode and funct fields to their widths. Immediates that do not fit
+//! their field and odd branch or jump offsets are rejected rather than
+//! truncated. This is checked over edge cases and pseudo-random operands from
+//! a simple LCG.
+//! ```rust
+//! use rysk::{EncodeError, Instruction, Register, Xlen};
+//!
+//! let mut state = 0x853C_49E6_748F_EA9Bu64;
+//! let mut next = move || {
+//!     state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+//!     (state >> 32) as u32
+//! };
+//! let edges = [
+//!     0, 1, 2, -1, -2, 0x7FF, 0x800, -0x800, -0x801, 0xFFE, 0xFFF, 0x1000, -0x1000, -0x1002,
+//!     0xF_FFFE, 0xF_FFFF, 0x10_0000, -0x10_0000, -0x10_0002, i32::MAX, i32::MIN
+//! ];
+//! for n in 0..10_000 {
+//!     // Sign-extend from a random width so every field sees values on both
+//!     // sides of its range.
+//!     let imm = match edges.get(n) {
+//!         Some(&imm) => imm,
+//!         None => u32::sign_extend(next(), next() % 24 + 1) as i32
+//!     };
+//!     let fits = |bits: u32| (-(1 << (bits - 1))..1 << (bits - 1)).contains(&(imm as i64));
+//!     let fields = next();
+//!     let opcode = fields as u8 & 0x7F;
+//!     let funct3 = (fields >> 7) as u8 & 0b111;
+//!     let funct7 = (fields >> 10) as u8 & 0x7F;
+//!     let register = |shift: u32| Register::new((fields >> shift) as u8 & 0b11111).unwrap();
+//!     let (rd, rs1, rs2) = (register(17), register(22), register(27));
+//!
+//!     let r = Instruction::encode_r(opcode, rd, funct3, rs1, rs2, funct7);
+//!     assert_eq!((r.opcode(), r.rd(), r.funct3(), r.rs1(), r.rs2(), r.funct7()), (opcode, rd, funct3, rs1, rs2, funct7));
+//!
+//!     let i = Instruction::encode_i(opcode, rd, funct3, rs1, imm);
+//!     let s = Instruction::encode_s(opcode, funct3, rs1, rs2, imm);
+//!     if fits(12) {
+//!         let (i, s) = (i.unwrap(), s.unwrap());
+//!         assert_eq!((i.opcode(), i.rd(), i.funct3(), i.rs1()), (opcode, rd, funct3, rs1));
+//!         assert_eq!(i.i_immediate_signed(), imm, "i {imm:#x}");
+//!         assert_eq!((s.opcode(), s.funct3(), s.rs1(), s.rs2()), (opcode, funct3, rs1, rs2));
+//!         assert_eq!(s.s_immediate_signed(), imm, "s {imm:#x}");
+//!     } else {
+//!         let error = Err(EncodeError::ImmediateOutOfRange { bits: 12, value: imm as i64 });
+//!         assert_eq!((i, s), (error, error), "i/s {imm:#x}");
+//!     }
+//!
+//!     let b = Instruction::encode_b(opcode, funct3, rs1, rs2, imm);
+//!     if !fits(13) {
+//!         assert_eq!(b, Err(EncodeError::ImmediateOutOfRange { bits: 13, value: imm as i64 }), "b {imm:#x}");
+//!     } else if imm & 1 != 0 {
+//!         assert_eq!(b, Err(EncodeError::MisalignedBranchTarget(imm as i64)), "b {imm:#x}");
+//!     } else {
+//!         let b = b.unwrap();
+//!         assert_eq!((b.opcode(), b.funct3(), b.rs1(), b.rs2()), (opcode, funct3, rs1, rs2));
+//!         assert_eq!(b.b_immediate_signed(), imm, "b {imm:#x}");
+//!         assert_eq!(b.b_immediate(), imm as u32);
+//!         assert_eq!(b.branch_target(0u64), imm as i64 as u64);
+//!     }
+//!
+//!     let upper = imm as u32;
+//!     let u = Instruction::encode_u(opcode, rd, upper);
+//!     if upper < 1 << 20 {
+//!         let u = u.unwrap();
+//!         assert_eq!((u.opcode(), u.rd()), (opcode, rd));
+//!         assert_eq!(u.u_immediate(), upper << 12, "u {upper:#x}");
+//!     } else {
+//!         assert_eq!(u, Err(EncodeError::ImmediateOutOfRange { bits: 20, value: upper as i64 }), "u {upper:#x}");
+//!     }
+//!
+//!     let j = Instruction::encode_j(opcode, rd, imm);
+//!     if !fits(21) {
+//!         assert_eq!(j, Err(EncodeError::ImmediateOutOfRange { bits: 21, value: imm as i64 }), "j {imm:#x}");
+//!     } else if imm & 1 != 0 {
+//!         assert_eq!(j, Err(EncodeError::MisalignedBranchTarget(imm as i64)), "j {imm:#x}");
+//!     } else {
+//!         let j = j.unwrap();
+//!         assert_eq!((j.opcode(), j.rd()), (opcode, rd));
+//!         assert_eq!(j.j_immediate_signed(), imm, "j {imm:#x}");
+//!         assert_eq!(j.j_immediate(), imm as u32);
+//!         assert_eq!(j.jal_target(0u64), imm as i64 as u64);
+//!     }
+//! }
+//! ```
+use super::{Instruction, InstructionPattern, Register};
+
+/// An operand that cannot be represented in the requested instruction format.
+///
+/// The encoders mask opcode and funct arguments to their field widths while
+/// register and immediate operands are validated. [`Instruction::encode_r`]
+/// cannot fail, as any register fits a 5-bit field.
+///
+/// The standard immediates accept:
+/// - I and S-type: `-2048..=2047`
+/// - B-type: even offsets in `-4096..=4094`
+/// - U-type: upper immediates in `0..=0xF_FFFF`
+/// - J-type: even offsets in `-0x10_0000..=0xF_FFFE`
+///
+/// Values outside the range are reported as out of range before an odd
+/// offset is reported as misaligned.
+/// ```rust
+/// use rysk::{EncodeError, Instruction, Register::{X0, X1}};
+/// let i = |imm| Instruction::encode_i(0b0010011, X1, 0, X1, imm).map(Instruction::i_immediate_signed);
+/// assert_eq!(i(2047), Ok(2047));
+/// assert_eq!(i(-2048), Ok(-2048));
+/// assert_eq!(i(-2049), Err(EncodeError::ImmediateOutOfRange { bits: 12, value: -2049 }));
+///
+/// let b = |offset| Instruction::encode_b(0b1100011, 0, X0, X1, offset).map(Instruction::b_immediate_signed);
+/// assert_eq!(b(4094), Ok(4094));
+/// assert_eq!(b(-4096), Ok(-4096));
+/// assert_eq!(b(4096), Err(EncodeError::ImmediateOutOfRange { bits: 13, value: 4096 }));
+/// assert_eq!(b(4095), Err(EncodeError::MisalignedBranchTarget(4095)));
+/// assert_eq!(b(-4097), Err(EncodeError::ImmediateOutOfRange { bits: 13, value: -4097 }));
+///
+/// let j = |offset| Instruction::encode_j(0b1101111, X1, offset).map(Instruction::j_immediate_signed);
+/// assert_eq!(j(0xF_FFFE), Ok(0xF_FFFE));
+/// assert_eq!(j(-0x10_0000), Ok(-0x10_0000));
+/// assert_eq!(j(-0x10_0002), Err(EncodeError::ImmediateOutOfRange { bits: 21, value: -0x10_0002 }));
+/// assert_eq!(j(-1), Err(EncodeError::MisalignedBranchTarget(-1)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EncodeError {
+    /// The register cannot be encoded in a 3-bit compressed register field,
+    /// which only addresses `x8` through `x15`.
+    Register(Register),
+    /// The immediate does not fit in a field of `bits` bits. Scaled fields
+    /// count the implied low zero bits.
+    ImmediateOutOfRange {
+        bits: u8,
+        value: i64
+    },
+    /// The immediate is not a multiple of the `align` bytes a scaled field
+    /// can represent.
+    MisalignedImmediate {
+        align: u8,
+        value: i64
+    },
+    /// The branch or jump offset is odd.
+    MisalignedBranchTarget(i64),
+    /// The immediate is reserved by the encoding, such as a zero `c.addi4spn`
+    /// offset.
+    ReservedImmediate(i64)
+}
+
+/// Return the 3-bit field for a compressed `x8`-`x15` register.
+#[inline]
+fn compressed_register(r: Register) -> Result<u16, EncodeError> {
+    match r as u8 {
+        n @ 8..=15 => Ok((n - 8) as u16),
+        _ => Err(EncodeError::Register(r))
+    }
+}
+/// Check that a signed immediate fits in `bits` bits.
+#[inline]
+fn signed(imm: i64, bits: u8) -> Result<i64, EncodeError> {
+    let limit = 1 << (bits - 1);
+    if imm < -limit || imm >= limit {
+        Err(EncodeError::ImmediateOutOfRange { bits, value: imm })
+    } else {
+        Ok(imm)
+    }
+}
+/// Check that an unsigned immediate fits in `bits` bits and is a multiple of
+/// `align`.
+#[inline]
+fn unsigned(imm: u32, bits: u8, align: u8) -> Result<u16, EncodeError> {
+    let value = imm as i64;
+    if value >= 1 << bits {
+        Err(EncodeError::ImmediateOutOfRange { bits, value })
+    } else if value % align as i64 != 0 {
+        Err(EncodeError::MisalignedImmediate { align, value })
+    } else {
+        Ok(imm as u16)
+    }
+}
+/// Check that a signed branch offset fits in `bits` bits and is even.
+#[inline]
+fn branch(offset: i32, bits: u8) -> Result<i64, EncodeError> {
+    let offset = signed(offset as i64, bits)?;
+    if offset & 1 != 0 {
+        Err(EncodeError::MisalignedBranchTarget(offset))
+    } else {
+        Ok(offset)
+    }
+}
+/// Move bits `hi..=lo` of `value` so that bit `lo` lands at bit `to`.
+#[inline]
+fn bits(value: u16, hi: u32, lo: u32, to: u32) -> u16 {
+    ((value >> lo) & ((1 << (hi - lo + 1)) - 1)) << to
+}
+/// Place the word or doubleword-scaled offset of a CL or CS-type instruction.
+#[inline]
+fn memory_offset(uimm: u32, doubleword: bool) -> Result<u16, EncodeError> {
+    Ok(if doubleword {
+        let uimm = unsigned(uimm, 8, 8)?;
+        bits(uimm, 5, 3, 10) | bits(uimm, 7, 6, 5)
+    } else {
+        let uimm = unsigned(uimm, 7, 4)?;
+        bits(uimm, 5, 3, 10) | bits(uimm, 2, 2, 6) | bits(uimm, 6, 6, 5)
+    })
+}
+
+impl Instruction {
+    /// Encode an R-type instruction.
+    /// ```rust
+    /// use rysk::{Instruction, Register};
+    /// // add a0,a1,a2
+    /// let i = Instruction::encode_r(0b0110011, Register::X10, 0b000, Register::X11, Register::X12, 0b0000000);
+    /// assert_eq!(i.raw(), 0x00c58533);
+    /// // Fields are masked to their widths.
+    /// assert_eq!(Instruction::encode_r(0xFF, Register::X0, 0xFF, Register::X0, Register::X0, 0xFF).raw(), 0xFE00_707F);
+    /// ```
+    pub fn encode_r(opcode: u8, rd: Register, funct3: u8, rs1: Register, rs2: Register, funct7: u8) -> Self {
+        InstructionPattern::new()
+            .opcode(opcode)
+            .rd(rd)
+            .funct3(funct3)
+            .rs1(rs1)
+            .rs2(rs2)
+            .funct7(funct7)
+            .instruction()
+    }
+    /// Encode an I-type instruction with a signed 12-bit immediate.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // addi a0,a1,-5
+    /// let i = Instruction::encode_i(0b0010011, Register::X10, 0b000, Register::X11, -5).unwrap();
+    /// assert_eq!(i.raw(), 0xffb58513);
+    /// assert_eq!(i.i_immediate_signed(), -5);
+    /// assert_eq!(
+    ///     Instruction::encode_i(0b0010011, Register::X10, 0b000, Register::X11, 2048).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 12, value: 2048 }
+    /// );
+    /// ```
+    pub fn encode_i(opcode: u8, rd: Register, funct3: u8, rs1: Register, imm: i32) -> Result<Self, EncodeError> {
+        let imm = signed(imm as i64, 12)?;
+        Ok(InstructionPattern::new()
+            .opcode(opcode)
+            .rd(rd)
+            .funct3(funct3)
+            .rs1(rs1)
+            .i_immediate(imm as u32)
+            .instruction())
+    }
+    /// Encode an S-type instruction with a signed 12-bit immediate.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // sw a2,-8(sp)
+    /// let i = Instruction::encode_s(0b0100011, 0b010, Register::X2, Register::X12, -8).unwrap();
+    /// assert_eq!(i.raw(), 0xfec12c23);
+    /// assert_eq!(i.s_immediate_signed(), -8);
+    /// assert_eq!(
+    ///     Instruction::encode_s(0b0100011, 0b010, Register::X2, Register::X12, -2049).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 12, value: -2049 }
+    /// );
+    /// ```
+    pub fn encode_s(opcode: u8, funct3: u8, rs1: Register, rs2: Register, imm: i32) -> Result<Self, EncodeError> {
+        let imm = signed(imm as i64, 12)?;
+        Ok(InstructionPattern::new()
+            .opcode(opcode)
+            .funct3(funct3)
+            .rs1(rs1)
+            .rs2(rs2)
+            .s_immediate(imm as u32)
+            .instruction())
+    }
+    /// Encode a B-type branch with a signed, even 13-bit offset.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // beq a0,a1,-8
+    /// let i = Instruction::encode_b(0b1100011, 0b000, Register::X10, Register::X11, -8).unwrap();
+    /// assert_eq!(i.raw(), 0xfeb50ce3);
+    /// assert_eq!(i.b_immediate_signed(), -8);
+    /// assert_eq!(
+    ///     Instruction::encode_b(0b1100011, 0b000, Register::X10, Register::X11, 5).unwrap_err(),
+    ///     EncodeError::MisalignedBranchTarget(5)
+    /// );
+    /// ```
+    pub fn encode_b(opcode: u8, funct3: u8, rs1: Register, rs2: Register, offset: i32) -> Result<Self, EncodeError> {
+        let offset = branch(offset, 13)?;
+        Ok(InstructionPattern::new()
+            .opcode(opcode)
+            .funct3(funct3)
+            .rs1(rs1)
+            .rs2(rs2)
+            .b_immediate(offset as u32)
+            .instruction())
+    }
+    /// Encode a U-type instruction from its 20-bit upper immediate, as written
+    /// in assembly.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // lui a0,0xdead4
+    /// let i = Instruction::encode_u(0b0110111, Register::X10, 0xDEAD4).unwrap();
+    /// assert_eq!(i.raw(), 0xdead4537);
+    /// assert_eq!(i.u_immediate(), 0xDEAD_4000);
+    /// assert_eq!(
+    ///     Instruction::encode_u(0b0110111, Register::X10, 0xDEAD_4000).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 20, value: 0xDEAD_4000 }
+    /// );
+    /// ```
+    pub fn encode_u(opcode: u8, rd: Register, upper: u32) -> Result<Self, EncodeError> {
+        if upper >= 1 << 20 {
+            return Err(EncodeError::ImmediateOutOfRange { bits: 20, value: upper as i64 });
+        }
+        Ok(InstructionPattern::new()
+            .opcode(opcode)
+            .rd(rd)
+            .u_immediate(upper << 12)
+            .instruction())
+    }
+    /// Encode a J-type jump with a signed, even 21-bit offset.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // jal ra,-4
+    /// let i = Instruction::encode_j(0b1101111, Register::X1, -4).unwrap();
+    /// assert_eq!(i.raw(), 0xffdff0ef);
+    /// assert_eq!(i.j_immediate_signed(), -4);
+    /// assert_eq!(
+    ///     Instruction::encode_j(0b1101111, Register::X1, 0x10_0000).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 21, value: 0x10_0000 }
+    /// );
+    /// ```
+    pub fn encode_j(opcode: u8, rd: Register, offset: i32) -> Result<Self, EncodeError> {
+        let offset = branch(offset, 21)?;
+        Ok(InstructionPattern::new()
+            .opcode(opcode)
+            .rd(rd)
+            .j_immediate(offset as u32)
+            .instruction())
+    }
+
+    #[inline]
+    fn compressed_parcel(parcel: u16) -> Self {
+        Self(parcel as u32)
+    }
+
+    /// Encode a CR-type instruction such as `c.jr`, `c.mv` or `c.add`.
+    /// ```rust
+    /// use rysk::{Instruction, Register};
+    /// // c.mv a0,a1
+    /// let i = Instruction::encode_cr(0b10, 0b1000, Register::X10, Register::X11);
+    /// assert_eq!(i.compressed_funct4(), 0b1000);
+    /// ```
+    pub fn encode_cr(op: u8, funct4: u8, rd_rs1: Register, rs2: Register) -> Self {
+        Self::compressed_parcel(
+            bits(funct4 as u16, 3, 0, 12) |
+            bits(rd_rs1 as u16, 4, 0, 7) |
+            bits(rs2 as u16, 4, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        )
+    }
+    /// Encode a CI-type instruction with a signed 6-bit immediate, as used by
+    /// `c.addi`, `c.addiw` and `c.li`.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // c.addi a0,-1
+    /// let i = Instruction::encode_ci(0b01, 0b000, Register::X10, -1).unwrap();
+    /// assert_eq!(i.compressed_rs1(), Register::X10);
+    /// let ci = |imm| Instruction::encode_ci(0b01, 0b000, Register::X10, imm).map(|i| i.ci_immediate() as i32);
+    /// assert_eq!(ci(-32), Ok(-32));
+    /// assert_eq!(ci(31), Ok(31));
+    /// assert_eq!(ci(-1), Ok(-1));
+    /// assert_eq!(
+    ///     Instruction::encode_ci(0b01, 0b000, Register::X10, 32).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 6, value: 32 }
+    /// );
+    /// ```
+    pub fn encode_ci(op: u8, funct3: u8, rd_rs1: Register, imm: i32) -> Result<Self, EncodeError> {
+        let imm = signed(imm as i64, 6)? as u16;
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            bits(imm, 5, 5, 12) |
+            bits(rd_rs1 as u16, 4, 0, 7) |
+            bits(imm, 4, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CSS-type instruction with a word-scaled stack offset, as used
+    /// by `c.swsp`.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // c.swsp a2,252(sp)
+    /// let i = Instruction::encode_css(0b10, 0b110, Register::X12, 252).unwrap();
+    /// assert_eq!(i.compressed_rs2(), Register::X12);
+    /// assert_eq!(i.css_immediate(), 252);
+    /// let css = |uimm| Instruction::encode_css(0b10, 0b110, Register::X12, uimm).map(Instruction::css_immediate);
+    /// assert_eq!(css(0), Ok(0));
+    /// assert_eq!(css(4), Ok(4));
+    /// assert_eq!(css(256), Err(EncodeError::ImmediateOutOfRange { bits: 8, value: 256 }));
+    /// assert_eq!(
+    ///     Instruction::encode_css(0b10, 0b110, Register::X12, 254).unwrap_err(),
+    ///     EncodeError::MisalignedImmediate { align: 4, value: 254 }
+    /// );
+    /// ```
+    pub fn encode_css(op: u8, funct3: u8, rs2: Register, uimm: u32) -> Result<Self, EncodeError> {
+        let uimm = unsigned(uimm, 8, 4)?;
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            bits(uimm, 5, 2, 9) |
+            bits(uimm, 7, 6, 7) |
+            bits(rs2 as u16, 4, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CIW-type instruction with a nonzero, word-scaled unsigned
+    /// immediate, as used by `c.addi4spn`.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // c.addi4spn s1,sp,1020
+    /// let i = Instruction::encode_ciw(0b00, 0b000, Register::X9, 1020).unwrap();
+    /// assert_eq!(i.crs2(), Register::X9);
+    /// assert_eq!(i.ciw_immediate(), 1020);
+    /// let ciw = |uimm| Instruction::encode_ciw(0b00, 0b000, Register::X9, uimm).map(Instruction::ciw_immediate);
+    /// assert_eq!(ciw(4), Ok(4));
+    /// assert_eq!(ciw(1024), Err(EncodeError::ImmediateOutOfRange { bits: 10, value: 1024 }));
+    /// // A zero immediate is reserved.
+    /// assert_eq!(ciw(0), Err(EncodeError::ReservedImmediate(0)));
+    /// ```
+    pub fn encode_ciw(op: u8, funct3: u8, rd: Register, uimm: u32) -> Result<Self, EncodeError> {
+        let rd = compressed_register(rd)?;
+        let uimm = match unsigned(uimm, 10, 4)? {
+            0 => return Err(EncodeError::ReservedImmediate(0)),
+            uimm => uimm
+        };
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            bits(uimm, 5, 4, 11) |
+            bits(uimm, 9, 6, 7) |
+            bits(uimm, 2, 2, 6) |
+            bits(uimm, 3, 3, 5) |
+            bits(rd, 2, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CL-type instruction with a word-scaled offset, as used by
+    /// `c.lw`, or a doubleword-scaled offset, as used by `c.ld` and `c.fld`.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // c.lw a5,124(a4)
+    /// let i = Instruction::encode_cl(0b00, 0b010, Register::X15, Register::X14, 124, false).unwrap();
+    /// assert_eq!(i.compressed_funct3(), 0b010);
+    /// assert_eq!(i.crs1(), Register::X14);
+    /// assert_eq!(i.crs2(), Register::X15);
+    /// assert_eq!(i.cl_immediate(), 124);
+    /// assert_eq!(
+    ///     Instruction::encode_cl(0b00, 0b010, Register::X1, Register::X14, 124, false).unwrap_err(),
+    ///     EncodeError::Register(Register::X1)
+    /// );
+    /// assert_eq!(
+    ///     Instruction::encode_cl(0b00, 0b010, Register::X15, Register::X14, 128, false).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 7, value: 128 }
+    /// );
+    ///
+    /// // c.fld fa5,248(a4)
+    /// let i = Instruction::encode_cl(0b00, 0b001, Register::X15, Register::X14, 248, true).unwrap();
+    /// assert_eq!(i.raw(), 0x3F7C);
+    /// assert_eq!(i.expand_rv32().unwrap().i_immediate(), 248);
+    /// assert_eq!(
+    ///     Instruction::encode_cl(0b00, 0b001, Register::X15, Register::X14, 252, true).unwrap_err(),
+    ///     EncodeError::MisalignedImmediate { align: 8, value: 252 }
+    /// );
+    /// ```
+    pub fn encode_cl(op: u8, funct3: u8, rd: Register, rs1: Register, uimm: u32, doubleword: bool) -> Result<Self, EncodeError> {
+        let rd = compressed_register(rd)?;
+        let rs1 = compressed_register(rs1)?;
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            memory_offset(uimm, doubleword)? |
+            bits(rs1, 2, 0, 7) |
+            bits(rd, 2, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CS-type instruction with a word-scaled offset, as used by
+    /// `c.sw`, or a doubleword-scaled offset, as used by `c.sd` and `c.fsd`.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // c.sw s0,64(a3)
+    /// let i = Instruction::encode_cs(0b00, 0b110, Register::X13, Register::X8, 64, false).unwrap();
+    /// assert_eq!(i.crs1(), Register::X13);
+    /// assert_eq!(i.crs2(), Register::X8);
+    /// assert_eq!(i.cs_immediate(), 64);
+    /// let cs = |uimm| Instruction::encode_cs(0b00, 0b110, Register::X13, Register::X8, uimm, false).map(Instruction::cs_immediate);
+    /// assert_eq!(cs(124), Ok(124));
+    /// assert_eq!(cs(0), Ok(0));
+    /// assert_eq!(cs(126), Err(EncodeError::MisalignedImmediate { align: 4, value: 126 }));
+    ///
+    /// // c.fsd fs0,248(a3)
+    /// let i = Instruction::encode_cs(0b00, 0b101, Register::X13, Register::X8, 248, true).unwrap();
+    /// assert_eq!(i.expand_rv32().unwrap().s_immediate(), 248);
+    /// assert_eq!(
+    ///     Instruction::encode_cs(0b00, 0b101, Register::X13, Register::X8, 256, true).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 8, value: 256 }
+    /// );
+    /// ```
+    pub fn encode_cs(op: u8, funct3: u8, rs1: Register, rs2: Register, uimm: u32, doubleword: bool) -> Result<Self, EncodeError> {
+        let rs1 = compressed_register(rs1)?;
+        let rs2 = compressed_register(rs2)?;
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            memory_offset(uimm, doubleword)? |
+            bits(rs1, 2, 0, 7) |
+            bits(rs2, 2, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CA-type instruction such as `c.and` or `c.sub`.
+    /// ```rust
+    /// use rysk::{Instruction, Register};
+    /// // c.and s1,a0
+    /// let i = Instruction::encode_ca(0b01, 0b100011, Register::X9, 0b11, Register::X10).unwrap();
+    /// assert_eq!(i.compressed_funct6(), 0b100011);
+    /// assert_eq!(i.compressed_funct2(), 0b11);
+    /// ```
+    pub fn encode_ca(op: u8, funct6: u8, rd_rs1: Register, funct2: u8, rs2: Register) -> Result<Self, EncodeError> {
+        let rd_rs1 = compressed_register(rd_rs1)?;
+        let rs2 = compressed_register(rs2)?;
+        Ok(Self::compressed_parcel(
+            bits(funct6 as u16, 5, 0, 10) |
+            bits(rd_rs1, 2, 0, 7) |
+            bits(funct2 as u16, 1, 0, 5) |
+            bits(rs2, 2, 0, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CB-type branch such as `c.beqz` with a signed, even offset.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction, Register};
+    /// // c.beqz a3,-256
+    /// let i = Instruction::encode_cb(0b01, 0b110, Register::X13, -256).unwrap();
+    /// assert_eq!(i.crs1(), Register::X13);
+    /// assert_eq!(i.cb_immediate() as i32, -256);
+    /// let cb = |offset| Instruction::encode_cb(0b01, 0b110, Register::X13, offset).map(|i| i.cb_immediate() as i32);
+    /// assert_eq!(cb(254), Ok(254));
+    /// assert_eq!(cb(-2), Ok(-2));
+    /// assert_eq!(cb(256), Err(EncodeError::ImmediateOutOfRange { bits: 9, value: 256 }));
+    /// assert_eq!(
+    ///     Instruction::encode_cb(0b01, 0b110, Register::X13, -3).unwrap_err(),
+    ///     EncodeError::MisalignedBranchTarget(-3)
+    /// );
+    /// ```
+    pub fn encode_cb(op: u8, funct3: u8, rs1: Register, offset: i32) -> Result<Self, EncodeError> {
+        let rs1 = compressed_register(rs1)?;
+        let offset = branch(offset, 9)? as u16;
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            bits(offset, 8, 8, 12) |
+            bits(offset, 4, 3, 10) |
+            bits(rs1, 2, 0, 7) |
+            bits(offset, 7, 6, 5) |
+            bits(offset, 2, 1, 3) |
+            bits(offset, 5, 5, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+    /// Encode a CJ-type jump such as `c.j` with a signed, even offset.
+    /// ```rust
+    /// use rysk::{EncodeError, Instruction};
+    /// // c.j 2046
+    /// let i = Instruction::encode_cj(0b01, 0b101, 2046).unwrap();
+    /// assert_eq!(i.compressed_funct3(), 0b101);
+    /// assert_eq!(i.cj_immediate(), 2046);
+    /// let cj = |offset| Instruction::encode_cj(0b01, 0b101, offset).map(|i| i.cj_immediate() as i32);
+    /// assert_eq!(cj(-2048), Ok(-2048));
+    /// assert_eq!(cj(-2), Ok(-2));
+    /// assert_eq!(
+    ///     Instruction::encode_cj(0b01, 0b101, 2048).unwrap_err(),
+    ///     EncodeError::ImmediateOutOfRange { bits: 12, value: 2048 }
+    /// );
+    /// ```
+    pub fn encode_cj(op: u8, funct3: u8, offset: i32) -> Result<Self, EncodeError> {
+        let offset = branch(offset, 12)? as u16;
+        Ok(Self::compressed_parcel(
+            bits(funct3 as u16, 2, 0, 13) |
+            bits(offset, 11, 11, 12) |
+            bits(offset, 4, 4, 11) |
+            bits(offset, 9, 8, 9) |
+            bits(offset, 10, 10, 8) |
+            bits(offset, 6, 6, 7) |
+            bits(offset, 7, 7, 6) |
+            bits(offset, 3, 1, 3) |
+            bits(offset, 5, 5, 2) |
+            bits(op as u16, 1, 0, 0)
+        ))
+    }
+}
@@ -0,0 +1,224 @@
+use super::{Instruction, InstructionPattern, Register};
+
+impl Instruction {
+    /// Expand an RV32C compressed instruction into the equivalent RV32
+    /// standard instruction, returning [`None`] for reserved and illegal
+    /// encodings.
+    ///
+    /// Standard instructions are returned unchanged. Parcels are always
+    /// decoded as RV32C, which reuses several RV64C encodings: the `c.addiw`
+    /// encoding expands to `c.jal`, `c.ld`, `c.sd`, `c.ldsp` and `c.sdsp` to
+    /// their single-precision floating-point forms, and shifts by 32 or more
+    /// and `c.subw`/`c.addw` are reserved. The expansion is therefore only
+    /// meaningful for an RV32 hart. Hints such as `c.nop` and `c.li x0` are
+    /// expanded like any other instruction.
+    /// ```rust
+    /// use rysk::Instruction;
+    /// fn expands(parcel: u16, standard: u32) {
+    ///     let (expanded, standard) = (
+    ///         Instruction::from_parcels([parcel, 0]).0.expand_rv32().unwrap(),
+    ///         Instruction::new(standard)
+    ///     );
+    ///     assert!(expanded.standard());
+    ///     assert_eq!(expanded.opcode(), standard.opcode());
+    ///     assert_eq!(expanded.funct3(), standard.funct3());
+    ///     assert_eq!(expanded.funct7(), standard.funct7());
+    ///     assert_eq!(expanded.rd(), standard.rd());
+    ///     assert_eq!(expanded.rs1(), standard.rs1());
+    ///     assert_eq!(expanded.rs2(), standard.rs2());
+    /// }
+    /// // c.addi4spn s1,sp,1020 => addi s1,sp,1020
+    /// expands(0x1FE4, 0x3fc10493);
+    /// // c.fld fa0,248(a5) => fld fa0,248(a5)
+    /// expands(0x3FE8, 0x0f87b507);
+    /// // c.lw a5,124(a4) => lw a5,124(a4)
+    /// expands(0x5F7C, 0x07c72783);
+    /// // c.flw fa1,64(s0) => flw fa1,64(s0)
+    /// expands(0x602C, 0x04042587);
+    /// // c.fsd fs1,8(a1) => fsd fs1,8(a1)
+    /// expands(0xA584, 0x0095b427);
+    /// // c.sw s0,64(a3) => sw s0,64(a3)
+    /// expands(0xC2A0, 0x0486a023);
+    /// // c.fsw fa5,124(a2) => fsw fa5,124(a2)
+    /// expands(0xFE7C, 0x06f62e27);
+    ///
+    /// // c.nop => addi x0,x0,0
+    /// expands(0x0001, 0x00000013);
+    /// // c.addi a0,-1 => addi a0,a0,-1
+    /// expands(0x157D, 0xfff50513);
+    /// // c.jal -2048 => jal ra,-2048
+    /// expands(0x3001, 0x801ff0ef);
+    /// // c.li a0,31 => addi a0,x0,31
+    /// expands(0x457D, 0x01f00513);
+    /// // c.addi16sp sp,-512 => addi sp,sp,-512
+    /// expands(0x7101, 0xe0010113);
+    /// // c.lui a5,0xfffe1 => lui a5,0xfffe1
+    /// expands(0x7785, 0xfffe17b7);
+    /// // c.lui t0,1 => lui t0,1
+    /// expands(0x6285, 0x000012b7);
+    /// // c.srli a0,31 => srli a0,a0,31
+    /// expands(0x817D, 0x01f55513);
+    /// // c.srai s1,1 => srai s1,s1,1
+    /// expands(0x8485, 0x4014d493);
+    /// // c.andi a2,-32 => andi a2,a2,-32
+    /// expands(0x9A01, 0xfe067613);
+    /// // c.sub s0,s1 => sub s0,s0,s1
+    /// expands(0x8C05, 0x40940433);
+    /// // c.xor a0,a5 => xor a0,a0,a5
+    /// expands(0x8D3D, 0x00f54533);
+    /// // c.or a1,a2 => or a1,a1,a2
+    /// expands(0x8DD1, 0x00c5e5b3);
+    /// // c.and a3,a4 => and a3,a3,a4
+    /// expands(0x8EF9, 0x00e6f6b3);
+    /// // c.j 2046 => jal x0,2046
+    /// expands(0xAFFD, 0x7fe0006f);
+    /// // c.beqz a3,-256 => beq a3,x0,-256
+    /// expands(0xD281, 0xf00680e3);
+    /// // c.bnez a0,254 => bne a0,x0,254
+    /// expands(0xED7D, 0x0e051f63);
+    ///
+    /// // c.slli t0,31 => slli t0,t0,31
+    /// expands(0x02FE, 0x01f29293);
+    /// // c.fldsp fs0,504(sp) => fld fs0,504(sp)
+    /// expands(0x347E, 0x1f813407);
+    /// // c.lwsp ra,252(sp) => lw ra,252(sp)
+    /// expands(0x50FE, 0x0fc12083);
+    /// // c.flwsp ft1,4(sp) => flw ft1,4(sp)
+    /// expands(0x6092, 0x00412087);
+    /// // c.jr ra => jalr x0,0(ra)
+    /// expands(0x8082, 0x00008067);
+    /// // c.mv a0,a1 => add a0,x0,a1
+    /// expands(0x852E, 0x00b00533);
+    /// // c.ebreak => ebreak
+    /// expands(0x9002, 0x00100073);
+    /// // c.jalr t0 => jalr ra,0(t0)
+    /// expands(0x9282, 0x000280e7);
+    /// // c.add sp,t1 => add sp,sp,t1
+    /// expands(0x911A, 0x00610133);
+    /// // c.fsdsp fs2,256(sp) => fsd fs2,256(sp)
+    /// expands(0xA24A, 0x11213027);
+    /// // c.swsp a2,252(sp) => sw a2,252(sp)
+    /// expands(0xDFB2, 0x0ec12e23);
+    /// // c.fswsp ft11,128(sp) => fsw ft11,128(sp)
+    /// expands(0xE17E, 0x09f12027);
+    ///
+    /// // Standard instructions are already expanded.
+    /// let sw = Instruction::new(0x0ec12e23);
+    /// assert_eq!(sw.expand_rv32().map(|i| i.s_immediate()), Some(252));
+    ///
+    /// let reserved = |parcel| Instruction::from_parcels([parcel, 0]).0.expand_rv32().is_none();
+    /// // The all-zero parcel is the canonical illegal instruction.
+    /// assert!(reserved(0x0000));
+    /// // c.addi16sp sp,0
+    /// assert!(reserved(0x6101));
+    /// // c.lui a5,0
+    /// assert!(reserved(0x6781));
+    /// // c.lwsp x0,0(sp)
+    /// assert!(reserved(0x4002));
+    /// // c.jr x0
+    /// assert!(reserved(0x8002));
+    /// // c.slli a0,32 is RV64 only
+    /// assert!(reserved(0x1502));
+    /// // c.addw a0,a1 is RV64 only
+    /// assert!(reserved(0x9D2D));
+    /// // The RV64 c.addiw a0,1 encoding is c.jal on RV32
+    /// let jal = Instruction::from_parcels([0x2505, 0]).0.expand_rv32().unwrap();
+    /// assert_eq!((jal.opcode(), jal.rd()), (0b1101111, rysk::Register::X1));
+    /// ```
+    pub fn expand_rv32(self) -> Option<Instruction> {
+        if self.standard() {
+            return Some(self);
+        }
+        // Full-sized and half-sized register fields
+        let (rd, rs2) = (self.compressed_rs1(), self.compressed_rs2());
+        let (rd_rs1, rs2_) = (self.crs1(), self.crs2());
+        // RV32 shift amounts must fit in 5 bits
+        let shamt = (self.0 & 0x1000 == 0).then_some(self.ci_immediate() & 0b1_1111);
+
+        let p = InstructionPattern::new();
+        let load = |funct3, rd, rs1, imm| p.opcode(0b0000011).funct3(funct3).rd(rd).rs1(rs1).i_immediate(imm);
+        let load_fp = |funct3, rd, rs1, imm| p.opcode(0b0000111).funct3(funct3).rd(rd).rs1(rs1).i_immediate(imm);
+        let store = |funct3, rs1, rs2, imm| p.opcode(0b0100011).funct3(funct3).rs1(rs1).rs2(rs2).s_immediate(imm);
+        let store_fp = |funct3, rs1, rs2, imm| p.opcode(0b0100111).funct3(funct3).rs1(rs1).rs2(rs2).s_immediate(imm);
+        let op_imm = |funct3, rd, rs1, imm| p.opcode(0b0010011).funct3(funct3).rd(rd).rs1(rs1).i_immediate(imm);
+        let op = |funct3, funct7, rd, rs1, rs2| p.opcode(0b0110011).funct3(funct3).funct7(funct7).rd(rd).rs1(rs1).rs2(rs2);
+        let jal = |rd, imm| p.opcode(0b1101111).rd(rd).j_immediate(imm);
+        let jalr = |rd, rs1| p.opcode(0b1100111).rd(rd).rs1(rs1);
+        let branch = |funct3, rs1, imm| p.opcode(0b1100011).funct3(funct3).rs1(rs1).rs2(Register::X0).b_immediate(imm);
+
+        let expanded = match (self.op(), self.compressed_funct3()) {
+            // Quadrant 0
+            (0b00, 0b000) => op_imm(0b000, rs2_, Register::X2, self.addi4spn_immediate()?),
+            (0b00, 0b001) => load_fp(0b011, rs2_, rd_rs1, self.cl_double_immediate()),
+            (0b00, 0b010) => load(0b010, rs2_, rd_rs1, self.cl_immediate()),
+            (0b00, 0b011) => load_fp(0b010, rs2_, rd_rs1, self.cl_immediate()),
+            (0b00, 0b101) => store_fp(0b011, rd_rs1, rs2_, self.cl_double_immediate()),
+            (0b00, 0b110) => store(0b010, rd_rs1, rs2_, self.cs_immediate()),
+            (0b00, 0b111) => store_fp(0b010, rd_rs1, rs2_, self.cs_immediate()),
+            // Quadrant 1
+            (0b01, 0b000) => op_imm(0b000, rd, rd, self.ci_immediate()),
+            (0b01, 0b001) => jal(Register::X1, self.cj_immediate()),
+            (0b01, 0b010) => op_imm(0b000, rd, Register::X0, self.ci_immediate()),
+            (0b01, 0b011) if rd == Register::X2 => op_imm(0b000, rd, rd, self.addi16sp_immediate()?),
+            (0b01, 0b011) => match self.ci_immediate() {
+                0 => return None,
+                imm => p.opcode(0b0110111).rd(rd).u_immediate(imm << 12)
+            },
+            (0b01, 0b100) => match ((self.0 >> 10) & 0b11, self.0 & 0x1000 != 0, self.compressed_funct2()) {
+                (0b00, _, _) => op_imm(0b101, rd_rs1, rd_rs1, shamt?),
+                (0b01, _, _) => op_imm(0b101, rd_rs1, rd_rs1, shamt? | 0x400),
+                (0b10, _, _) => op_imm(0b111, rd_rs1, rd_rs1, self.ci_immediate()),
+                (0b11, false, 0b00) => op(0b000, 0b0100000, rd_rs1, rd_rs1, rs2_),
+                (0b11, false, 0b01) => op(0b100, 0b0000000, rd_rs1, rd_rs1, rs2_),
+                (0b11, false, 0b10) => op(0b110, 0b0000000, rd_rs1, rd_rs1, rs2_),
+                (0b11, false, 0b11) => op(0b111, 0b0000000, rd_rs1, rd_rs1, rs2_),
+                _ => return None
+            },
+            (0b01, 0b101) => jal(Register::X0, self.cj_immediate()),
+            (0b01, 0b110) => branch(0b000, rd_rs1, self.cb_immediate()),
+            (0b01, 0b111) => branch(0b001, rd_rs1, self.cb_immediate()),
+            // Quadrant 2
+            (0b10, 0b000) => op_imm(0b001, rd, rd, shamt?),
+            (0b10, 0b001) => load_fp(0b011, rd, Register::X2, self.ldsp_immediate()),
+            (0b10, 0b010) if rd == Register::X0 => return None,
+            (0b10, 0b010) => load(0b010, rd, Register::X2, self.lwsp_immediate()),
+            (0b10, 0b011) => load_fp(0b010, rd, Register::X2, self.lwsp_immediate()),
+            (0b10, 0b100) => match (self.0 & 0x1000 != 0, rd, rs2) {
+                (false, Register::X0, Register::X0) => return None,
+                (false, rs1, Register::X0) => jalr(Register::X0, rs1),
+                (false, rd, rs2) => op(0b000, 0b0000000, rd, Register::X0, rs2),
+                (true, Register::X0, Register::X0) => return Some(Instruction(0x0010_0073)),
+                (true, rs1, Register::X0) => jalr(Register::X1, rs1),
+                (true, rd, rs2) => op(0b000, 0b0000000, rd, rd, rs2)
+            },
+            (0b10, 0b101) => store_fp(0b011, Register::X2, rs2, self.sdsp_immediate()),
+            (0b10, 0b110) => store(0b010, Register::X2, rs2, self.css_immediate()),
+            (0b10, 0b111) => store_fp(0b010, Register::X2, rs2, self.css_immediate()),
+            _ => return None
+        };
+        Some(expanded.instruction())
+    }
+
+    /// Return the zero-extended doubleword offset of a `c.fld` or `c.fsd`.
+    fn cl_double_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b0011_1000) |
+        ((self.0 << 1) & 0b1100_0000)
+    }
+    /// Return the zero-extended word offset of a `c.lwsp` or `c.flwsp`.
+    fn lwsp_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b0010_0000) |
+        ((self.0 >> 2) & 0b0001_1100) |
+        ((self.0 << 4) & 0b1100_0000)
+    }
+    /// Return the zero-extended doubleword offset of a `c.fldsp`.
+    fn ldsp_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b0_0010_0000) |
+        ((self.0 >> 2) & 0b0_0001_1000) |
+        ((self.0 << 4) & 0b1_1100_0000)
+    }
+    /// Return the zero-extended doubleword offset of a `c.fsdsp`.
+    fn sdsp_immediate(self) -> u32 {
+        ((self.0 >> 7) & 0b0_0011_1000) |
+        ((self.0 >> 1) & 0b1_1100_0000)
+    }
+}
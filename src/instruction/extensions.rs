@@ -0,0 +1,200 @@
+use core::ops::{BitOr, BitOrAssign};
+
+use super::{AmoOp, Instruction, Opcode, RoundingMode, SystemOp};
+
+/// A set of ISA extensions, combined with `|`.
+/// ```rust
+/// use rysk::Extensions;
+/// let rv32imac = Extensions::I | Extensions::M | Extensions::A | Extensions::C;
+/// assert!(rv32imac.contains(Extensions::M | Extensions::C));
+/// assert!(!rv32imac.contains(Extensions::F));
+/// assert!(Extensions::all().contains(rv32imac));
+/// assert_eq!(Extensions::empty(), Extensions::default());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub struct Extensions(u16);
+impl Extensions {
+    /// The base integer instruction set.
+    pub const I: Self = Self(1 << 0);
+    /// Integer multiplication and division.
+    pub const M: Self = Self(1 << 1);
+    /// Atomic instructions.
+    pub const A: Self = Self(1 << 2);
+    /// Single-precision floating-point.
+    pub const F: Self = Self(1 << 3);
+    /// Double-precision floating-point.
+    pub const D: Self = Self(1 << 4);
+    /// Compressed instructions.
+    pub const C: Self = Self(1 << 5);
+    /// Control and status register instructions.
+    pub const ZICSR: Self = Self(1 << 6);
+    /// The instruction-fetch fence.
+    pub const ZIFENCEI: Self = Self(1 << 7);
+
+    /// Return the empty set.
+    #[inline]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+    /// Return the set of every known extension.
+    #[inline]
+    pub const fn all() -> Self {
+        Self(0xFF)
+    }
+    /// Returns `true` if every extension in `other` is in the set.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+impl BitOr for Extensions {
+    type Output = Self;
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+impl BitOrAssign for Extensions {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0
+    }
+}
+
+impl Instruction {
+    /// Returns `true` if the instruction is a defined RV32 instruction of the
+    /// extensions in `ext`.
+    ///
+    /// The privileged `mret`, `sret` and `wfi` are legal with the base set,
+    /// while instructions only defined for RV64 are never legal. Compressed
+    /// instructions require both C and the extension of their expansion.
+    /// ```rust
+    /// use rysk::{Extensions, Instruction};
+    /// let rv32imac = Extensions::I | Extensions::M | Extensions::A | Extensions::C;
+    /// let legal = |i: u32, ext| Instruction::new(i).is_legal(ext);
+    /// // addi a0,a1,4
+    /// assert!(legal(0x00458513, Extensions::I));
+    /// // amoadd.w a0,a1,(a2)
+    /// assert!(legal(0x00b6252f, rv32imac));
+    /// assert!(!legal(0x00b6252f, Extensions::I | Extensions::M));
+    /// // lr.w a0,(a2) and sc.w a0,a1,(a2)
+    /// assert!(legal(0x1006252f, Extensions::A) && legal(0x18b6252f, Extensions::A));
+    /// // mul a0,a1,a2
+    /// assert!(legal(0x02c58533, rv32imac));
+    /// assert!(!legal(0x02c58533, Extensions::I));
+    /// // fadd.s fa0,fa1,fa2 and fadd.d fa0,fa1,fa2
+    /// assert!(!legal(0x00c5f553, rv32imac));
+    /// assert!(legal(0x00c5f553, Extensions::F));
+    /// assert!(!legal(0x02c5f553, Extensions::F));
+    /// assert!(legal(0x02c5f553, Extensions::D));
+    /// // fadd.s fa0,fa1,fa2 with the reserved rounding mode 0b101
+    /// assert!(!legal(0x00c5d553, Extensions::F));
+    /// // flw fa0,4(a0) and fld fa0,8(a0)
+    /// assert!(legal(0x00452507, Extensions::F) && !legal(0x00452507, Extensions::D));
+    /// assert!(legal(0x00853507, Extensions::D));
+    /// // fsqrt.s fa0,fa1, fmv.x.w a0,fa0 and fcvt.w.s a0,fa0
+    /// assert!(legal(0x5805f553, Extensions::F) && legal(0xe0050553, Extensions::F) && legal(0xc0057553, Extensions::F));
+    /// // fcvt.d.s fa0,fa1, fclass.d a0,fa0 and fmadd.d fa0,fa1,fa2,fa3
+    /// assert!(legal(0x42058553, Extensions::D) && legal(0xe2051553, Extensions::D) && legal(0x6ac5f543, Extensions::D));
+    /// assert!(!legal(0x42058553, Extensions::F));
+    /// // csrrw a0,mstatus,a1
+    /// assert!(legal(0x30059573, Extensions::ZICSR) && !legal(0x30059573, rv32imac));
+    /// // fence.i
+    /// assert!(legal(0x0000100f, Extensions::ZIFENCEI) && !legal(0x0000100f, rv32imac));
+    /// // ecall and mret
+    /// assert!(legal(0x00000073, Extensions::I) && legal(0x30200073, Extensions::I));
+    /// // addw a0,a1,a2 and amoadd.d a0,a1,(a2) are RV64 only
+    /// assert!(!legal(0x00c5853b, Extensions::all()) && !legal(0x00b6352f, Extensions::all()));
+    /// assert!(!legal(0xffffffff, Extensions::all()));
+    ///
+    /// let compressed = |parcel: u16, ext| Instruction::from_parcels([parcel, 0]).0.is_legal(ext);
+    /// // c.addi a0,-1
+    /// assert!(compressed(0x157D, rv32imac));
+    /// assert!(!compressed(0x157D, Extensions::I | Extensions::M | Extensions::A));
+    /// // c.fld fa0,8(a1)
+    /// assert!(!compressed(0x2588, rv32imac));
+    /// assert!(compressed(0x2588, Extensions::C | Extensions::D));
+    /// assert!(!compressed(0x0000, Extensions::all()));
+    /// ```
+    pub fn is_legal(self, ext: Extensions) -> bool {
+        if self.compressed() {
+            return ext.contains(Extensions::C) && self.expand_rv32().is_some_and(|i| i.is_legal(ext));
+        }
+        let (funct3, funct7) = (self.funct3(), self.funct7());
+        // Floating-point format fields select F or D.
+        let format = |fmt: u8| match fmt {
+            0b00 => ext.contains(Extensions::F),
+            0b01 => ext.contains(Extensions::D),
+            _ => false
+        };
+        let rounding = || !matches!(self.rounding_mode(), RoundingMode::Reserved(_));
+        let base = ext.contains(Extensions::I);
+        match self.decoded_opcode() {
+            Some(Opcode::Lui | Opcode::Auipc | Opcode::Jal) => base,
+            Some(Opcode::Jalr) => base && funct3 == 0b000,
+            Some(Opcode::Branch) => base && !matches!(funct3, 0b010 | 0b011),
+            Some(Opcode::Load) => base && !matches!(funct3, 0b011 | 0b110 | 0b111),
+            Some(Opcode::Store) => base && funct3 <= 0b010,
+            Some(Opcode::OpImm) => base && match funct3 {
+                0b001 => funct7 == 0b0000000,
+                0b101 => matches!(funct7, 0b0000000 | 0b0100000),
+                _ => true
+            },
+            Some(Opcode::Op) => match funct7 {
+                0b0000000 => base,
+                0b0100000 => base && matches!(funct3, 0b000 | 0b101),
+                0b0000001 => ext.contains(Extensions::M),
+                _ => false
+            },
+            Some(Opcode::MiscMem) => match funct3 {
+                0b000 => base,
+                0b001 => ext.contains(Extensions::ZIFENCEI),
+                _ => false
+            },
+            Some(Opcode::System) => match self.system_op() {
+                Some(SystemOp::Csr { .. }) => ext.contains(Extensions::ZICSR),
+                Some(_) => base,
+                None => false
+            },
+            Some(Opcode::Amo) => ext.contains(Extensions::A) && funct3 == 0b010 && match self.amo_funct5() {
+                // lr.w
+                0b00010 => self.rs2() as u8 == 0,
+                // sc.w
+                0b00011 => true,
+                funct5 => AmoOp::from_funct5(funct5).is_some()
+            },
+            Some(Opcode::LoadFp | Opcode::StoreFp) => match funct3 {
+                0b010 => ext.contains(Extensions::F),
+                0b011 => ext.contains(Extensions::D),
+                _ => false
+            },
+            Some(Opcode::Madd | Opcode::Msub | Opcode::Nmsub | Opcode::Nmadd) => format(self.r4_funct2()) && rounding(),
+            Some(Opcode::OpFp) => {
+                let rs2 = self.rs2() as u8;
+                let single = funct7 & 0b11 == 0b00;
+                format(funct7 & 0b11) && match funct7 >> 2 {
+                    // fadd, fsub, fmul and fdiv
+                    0b00000..=0b00011 => rounding(),
+                    // fsqrt
+                    0b01011 => rs2 == 0 && rounding(),
+                    // fsgnj, fsgnjn and fsgnjx
+                    0b00100 => funct3 <= 0b010,
+                    // fmin and fmax
+                    0b00101 => funct3 <= 0b001,
+                    // fcvt.s.d and fcvt.d.s
+                    0b01000 => ext.contains(Extensions::D) && rs2 == single as u8 && rounding(),
+                    // feq, flt and fle
+                    0b10100 => funct3 <= 0b010,
+                    // fcvt.w and fcvt.wu from and to floating-point
+                    0b11000 | 0b11010 => rs2 <= 1 && rounding(),
+                    // fmv.x.w and fclass
+                    0b11100 => rs2 == 0 && (funct3 == 0b001 || (funct3 == 0b000 && single)),
+                    // fmv.w.x
+                    0b11110 => rs2 == 0 && funct3 == 0b000 && single,
+                    _ => false
+                }
+            },
+            _ => false
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use super::{Instruction, Opcode};
+
+/// The encoding format of an instruction, which determines where its
+/// immediate and register fields are held.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Format {
+    R,
+    I,
+    S,
+    B,
+    U,
+    J,
+    /// The fused multiply-add format with a third source register.
+    R4,
+    Compressed(CFormat)
+}
+/// The encoding format of a compressed instruction.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CFormat {
+    /// Register
+    Cr,
+    /// Immediate
+    Ci,
+    /// Stack-relative store
+    Css,
+    /// Wide immediate
+    Ciw,
+    /// Load
+    Cl,
+    /// Store
+    Cs,
+    /// Arithmetic
+    Ca,
+    /// Branch
+    Cb,
+    /// Jump
+    Cj
+}
+
+impl Instruction {
+    /// Return the encoding format of the instruction, or `None` for reserved,
+    /// custom and vector opcodes.
+    ///
+    /// `fence` and the SYSTEM instructions are classed as I-type, as their
+    /// fields occupy the I-type immediate. Compressed instructions are
+    /// classified as RV32C, so `c.jal` rather than `c.addiw`.
+    /// ```rust
+    /// use rysk::{CFormat, Format, Instruction};
+    /// let format = |i: u32| Instruction::new(i).format();
+    /// // add a0,a1,a2
+    /// assert_eq!(format(0x00c58533), Some(Format::R));
+    /// // addw a0,a1,a2
+    /// assert_eq!(format(0x00c5853b), Some(Format::R));
+    /// // fadd.s fa0,fa1,fa2
+    /// assert_eq!(format(0x00c5f553), Some(Format::R));
+    /// // amoswap.w a0,a1,(a2)
+    /// assert_eq!(format(0x08b6252f), Some(Format::R));
+    /// // fmadd.s fa0,fa1,fa2,fa3
+    /// assert_eq!(format(0x68c5f543), Some(Format::R4));
+    /// // lw t0,8(sp)
+    /// assert_eq!(format(0x00812283), Some(Format::I));
+    /// // fld fa0,8(a0)
+    /// assert_eq!(format(0x00853507), Some(Format::I));
+    /// // addi a0,a1,4
+    /// assert_eq!(format(0x00458513), Some(Format::I));
+    /// // addiw a0,a1,1
+    /// assert_eq!(format(0x0015851b), Some(Format::I));
+    /// // jalr x31,1234(x11)
+    /// assert_eq!(format(0x4d258fe7), Some(Format::I));
+    /// // fence rw,rw
+    /// assert_eq!(format(0x0330000f), Some(Format::I));
+    /// // fence.i
+    /// assert_eq!(format(0x0000100f), Some(Format::I));
+    /// // ecall
+    /// assert_eq!(format(0x00000073), Some(Format::I));
+    /// // csrrw a0,mstatus,a1
+    /// assert_eq!(format(0x30059573), Some(Format::I));
+    /// // sw s0,-8(sp)
+    /// assert_eq!(format(0xfe812c23), Some(Format::S));
+    /// // fsw fa0,8(a0)
+    /// assert_eq!(format(0x00a52427), Some(Format::S));
+    /// // bgeu x10,x8,-4
+    /// assert_eq!(format(0xfe857ee3), Some(Format::B));
+    /// // lui a5,0xdead4
+    /// assert_eq!(format(0xdead47b7), Some(Format::U));
+    /// // auipc t0,0x1
+    /// assert_eq!(format(0x00001297), Some(Format::U));
+    /// // jal x7,0xDEAD4
+    /// assert_eq!(format(0x2d5de3ef), Some(Format::J));
+    /// // custom-0
+    /// assert_eq!(format(0x0000000b), None);
+    /// // Reserved
+    /// assert_eq!(format(0x0000006b), None);
+    ///
+    /// let compressed = |parcel: u16| Instruction::from_parcels([parcel, 0]).0.format();
+    /// for (parcel, format) in [
+    ///     (0x0808, CFormat::Ciw), // c.addi4spn a0,sp,16
+    ///     (0x41c8, CFormat::Cl),  // c.lw a0,4(a1)
+    ///     (0xc1c8, CFormat::Cs),  // c.sw a0,4(a1)
+    ///     (0xa588, CFormat::Cs),  // c.fsd fa0,8(a1)
+    ///     (0x157d, CFormat::Ci),  // c.addi a0,-1
+    ///     (0x2801, CFormat::Cj),  // c.jal 16
+    ///     (0x450d, CFormat::Ci),  // c.li a0,3
+    ///     (0x8109, CFormat::Cb),  // c.srli a0,2
+    ///     (0x890d, CFormat::Cb),  // c.andi a0,3
+    ///     (0x8d0d, CFormat::Ca),  // c.sub a0,a1
+    ///     (0xbff5, CFormat::Cj),  // c.j -4
+    ///     (0xc501, CFormat::Cb),  // c.beqz a0,8
+    ///     (0x050e, CFormat::Ci),  // c.slli a0,3
+    ///     (0x4522, CFormat::Ci),  // c.lwsp a0,8(sp)
+    ///     (0x8082, CFormat::Cr),  // c.jr ra
+    ///     (0x952e, CFormat::Cr),  // c.add a0,a1
+    ///     (0xc42a, CFormat::Css), // c.swsp a0,8(sp)
+    /// ] {
+    ///     assert_eq!(compressed(parcel), Some(Format::Compressed(format)), "{parcel:#06x}");
+    /// }
+    /// // The defined illegal instruction
+    /// assert_eq!(compressed(0x0000), None);
+    /// ```
+    pub fn format(self) -> Option<Format> {
+        if self.compressed() {
+            return self.compressed_format().map(Format::Compressed);
+        }
+        Some(match self.decoded_opcode()? {
+            Opcode::Op | Opcode::Op32 | Opcode::OpFp | Opcode::Amo => Format::R,
+            Opcode::Madd | Opcode::Msub | Opcode::Nmsub | Opcode::Nmadd => Format::R4,
+            Opcode::Load | Opcode::LoadFp | Opcode::MiscMem | Opcode::OpImm | Opcode::OpImm32 | Opcode::Jalr | Opcode::System => Format::I,
+            Opcode::Store | Opcode::StoreFp => Format::S,
+            Opcode::Branch => Format::B,
+            Opcode::Lui | Opcode::Auipc => Format::U,
+            Opcode::Jal => Format::J,
+            Opcode::OpV | Opcode::Custom0 | Opcode::Custom1 | Opcode::Custom2 | Opcode::Custom3 => return None
+        })
+    }
+    fn compressed_format(self) -> Option<CFormat> {
+        if self.0 as u16 == 0 {
+            return None;
+        }
+        Some(match (self.op(), self.compressed_funct3()) {
+            (0b00, 0b000) => CFormat::Ciw,
+            (0b00, 0b001..=0b011) => CFormat::Cl,
+            (0b00, 0b101..=0b111) => CFormat::Cs,
+            (0b01, 0b001 | 0b101) => CFormat::Cj,
+            (0b01, 0b100) if self.compressed_funct6() & 0b11 == 0b11 => CFormat::Ca,
+            (0b01, 0b100 | 0b110 | 0b111) => CFormat::Cb,
+            (0b01, _) => CFormat::Ci,
+            (0b10, 0b100) => CFormat::Cr,
+            (0b10, 0b101..=0b111) => CFormat::Css,
+            (0b10, _) => CFormat::Ci,
+            _ => return None
+        })
+    }
+}
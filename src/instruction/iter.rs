@@ -0,0 +1,48 @@
+use super::Instruction;
+
+/// Iterate over the instructions encoded in `bytes`.
+///
+/// Each item is the byte offset of the instruction, the instruction and a bit
+/// indicating that it was compressed. Iteration stops once the remaining bytes
+/// cannot hold the next instruction, such as a trailing odd byte or a
+/// truncated standard instruction.
+/// ```rust
+/// use rysk::instruction::iter;
+/// let bytes = [
+///     0x7D, 0x15,             // c.addi a0,-1
+///     0x93, 0x85, 0x45, 0x00, // addi a1,a1,4
+///     0x82, 0x80,             // c.jr ra
+///     0x13,                   // A trailing odd byte
+/// ];
+/// let mut instructions = iter(&bytes);
+/// let (offset, i, compressed) = instructions.next().unwrap();
+/// assert_eq!((offset, i.compressed_rs1(), compressed), (0, rysk::Register::X10, true));
+/// let (offset, i, compressed) = instructions.next().unwrap();
+/// assert_eq!((offset, i.i_immediate(), compressed), (2, 4, false));
+/// let (offset, _, compressed) = instructions.next().unwrap();
+/// assert_eq!((offset, compressed), (6, true));
+/// assert!(instructions.next().is_none());
+///
+/// // A standard instruction missing its second parcel ends the stream.
+/// assert_eq!(iter(&[0x7D, 0x15, 0x93, 0x85]).count(), 1);
+/// ```
+pub fn iter(bytes: &[u8]) -> InstructionIter<'_> {
+    InstructionIter { bytes, offset: 0 }
+}
+
+/// An iterator over the instructions encoded in a byte slice, created by
+/// [`iter`].
+#[derive(Clone, Debug)]
+pub struct InstructionIter<'a> {
+    bytes: &'a [u8],
+    offset: usize
+}
+impl Iterator for InstructionIter<'_> {
+    type Item = (usize, Instruction, bool);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (instruction, length) = Instruction::from_bytes(&self.bytes[self.offset..])?;
+        let offset = self.offset;
+        self.offset += length;
+        Some((offset, instruction, instruction.compressed()))
+    }
+}
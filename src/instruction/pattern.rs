@@ -0,0 +1,128 @@
+use super::{Instruction, Register};
+
+/// A standard instruction shape with wildcard fields.
+///
+/// Each builder method fixes one field; unset fields match anything. The
+/// immediate methods fix every immediate bit of the given format.
+/// ```rust
+/// use rysk::{Instruction, InstructionPattern, Register};
+/// // Any lw from sp
+/// let pattern = InstructionPattern::new()
+///     .opcode(0b0000011)
+///     .funct3(0b010)
+///     .rs1(Register::X2);
+/// // lw t0,8(sp)
+/// assert!(pattern.matches(Instruction::new(0x00812283)));
+/// // lw t1,-8(sp)
+/// assert!(pattern.matches(Instruction::new(0xff812303)));
+/// // lw t0,8(a0)
+/// assert!(!pattern.matches(Instruction::new(0x00852283)));
+/// // lw t0,8(sp) specifically
+/// assert!(pattern.rd(Register::X5).i_immediate(8).matches(Instruction::new(0x00812283)));
+/// assert!(!pattern.i_immediate(8).matches(Instruction::new(0xff812303)));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct InstructionPattern {
+    mask: u32,
+    value: u32
+}
+impl InstructionPattern {
+    /// Create a pattern matching any instruction.
+    #[inline]
+    pub fn new() -> Self {
+        Self { mask: 0, value: 0 }
+    }
+    /// Create a pattern matching instructions whose bits selected by `mask`
+    /// equal those of `value`.
+    #[inline]
+    pub fn from_mask(mask: u32, value: u32) -> Self {
+        Self { mask, value: value & mask }
+    }
+    /// Returns `true` if `instruction` has the shape described by the pattern.
+    #[inline]
+    pub fn matches(self, instruction: Instruction) -> bool {
+        instruction.0 & self.mask == self.value
+    }
+
+    /// Return the instruction with every unset field zeroed.
+    #[inline]
+    pub(super) fn instruction(self) -> Instruction {
+        Instruction(self.value)
+    }
+
+    #[inline]
+    fn field(self, mask: u32, value: u32) -> Self {
+        Self {
+            mask: self.mask | mask,
+            value: (self.value & !mask) | (value & mask)
+        }
+    }
+    /// Match the standard opcode.
+    pub fn opcode(self, opcode: u8) -> Self {
+        self.field(0x0000_007F, opcode as u32)
+    }
+    /// Match the standard funct3.
+    pub fn funct3(self, funct3: u8) -> Self {
+        self.field(0x0000_7000, (funct3 as u32) << 12)
+    }
+    /// Match the standard funct7.
+    pub fn funct7(self, funct7: u8) -> Self {
+        self.field(0xFE00_0000, (funct7 as u32) << 25)
+    }
+    /// Match the standard destination register.
+    pub fn rd(self, rd: Register) -> Self {
+        self.field(0x0000_0F80, (rd as u32) << 7)
+    }
+    /// Match the first standard source register.
+    pub fn rs1(self, rs1: Register) -> Self {
+        self.field(0x000F_8000, (rs1 as u32) << 15)
+    }
+    /// Match the second standard source register.
+    pub fn rs2(self, rs2: Register) -> Self {
+        self.field(0x01F0_0000, (rs2 as u32) << 20)
+    }
+
+    /// Match the I-type immediate value.
+    pub fn i_immediate(self, imm: u32) -> Self {
+        self.field(0xFFF0_0000, imm << 20)
+    }
+    /// Match the S-type immediate value.
+    pub fn s_immediate(self, imm: u32) -> Self {
+        self.field(0xFE00_0F80, ((imm << 20) & 0xFE00_0000) | ((imm << 7) & 0x0000_0F80))
+    }
+    /// Match the B-type immediate value.
+    /// ```rust
+    /// use rysk::{Instruction, InstructionPattern};
+    /// // beq a0,a1,-8
+    /// let beq = Instruction::new(0xfeb50ce3);
+    /// assert!(InstructionPattern::new().b_immediate(-8i32 as u32).matches(beq));
+    /// assert!(!InstructionPattern::new().b_immediate(-4i32 as u32).matches(beq));
+    /// ```
+    pub fn b_immediate(self, imm: u32) -> Self {
+        self.field(0xFE00_0F80,
+            ((imm << 19) & 0x8000_0000) |
+            ((imm << 20) & 0x7E00_0000) |
+            ((imm << 7)  & 0x0000_0F00) |
+            ((imm >> 4)  & 0x0000_0080)
+        )
+    }
+    /// Match the U-type immediate value.
+    pub fn u_immediate(self, imm: u32) -> Self {
+        self.field(0xFFFF_F000, imm)
+    }
+    /// Match the J-type immediate value.
+    /// ```rust
+    /// use rysk::{Instruction, InstructionPattern, Register};
+    /// // jal ra,2048
+    /// let call = InstructionPattern::new().opcode(0b1101111).rd(Register::X1);
+    /// assert!(call.j_immediate(2048).matches(Instruction::new(0x001000ef)));
+    /// ```
+    pub fn j_immediate(self, imm: u32) -> Self {
+        self.field(0xFFFF_F000,
+            ((imm << 11) & 0x8000_0000) |
+            ((imm << 20) & 0x7FE0_0000) |
+            ((imm << 9)  & 0x0010_0000) |
+            (imm         & 0x000F_F000)
+        )
+    }
+}
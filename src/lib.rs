@@ -1,20 +1,99 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "defmt")]
+mod defmt;
 pub mod env;
-mod instruction;
+pub mod instruction;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "std")]
+pub mod trace;
 
-pub use env::{Addressable, Cause, Hart};
-pub use instruction::{Instruction, Register};
+pub use env::{Addressable, Cause, Hart, TrapInfo};
+pub use instruction::{
+    AluOp, AmoOp, AmoOrdering, BranchKind, CFormat, CsrOp, CsrSource, Decoded, EncodeError, Extensions, FRegister,
+    Format, Instruction, InstructionPattern, LoadWidth, MulDivOp, Opcode, Register, RegisterRole, RoundingMode, StoreWidth,
+    SystemOp
+};
 
-pub trait Xlen {
+pub trait Xlen: Copy + Eq + Ord {
     const BITS: usize;
     const MAX: usize;
+    /// Zero-extend the value to 64 bits.
+    fn to_u64(self) -> u64;
+    /// Truncate a 64-bit value to XLEN bits.
+    fn from_u64(value: u64) -> Self;
+    /// Sign-extend the low `bits` bits of `value` to XLEN bits, where `bits`
+    /// is between 1 and XLEN.
+    /// ```rust
+    /// use rysk::Xlen;
+    /// assert_eq!(u32::sign_extend(0b1, 1), u32::MAX);
+    /// assert_eq!(u32::sign_extend(0b10, 1), 0);
+    /// assert_eq!(u32::sign_extend(0xFFF, 12), 0xFFFF_FFFF);
+    /// assert_eq!(u32::sign_extend(0x7FF, 12), 0x7FF);
+    /// assert_eq!(u32::sign_extend(0x8000_0000, 32), 0x8000_0000);
+    /// assert_eq!(u64::sign_extend(0x8000_0000, 32), 0xFFFF_FFFF_8000_0000);
+    /// assert_eq!(u64::sign_extend(0x1234_8000_0000, 32), 0xFFFF_FFFF_8000_0000);
+    /// assert_eq!(u64::sign_extend(0x8000_0000_0000_0000, 64), 0x8000_0000_0000_0000);
+    /// ```
+    fn sign_extend(value: Self, bits: u32) -> Self;
+    /// Zero-extend the low `bits` bits of `value` to XLEN bits, where `bits`
+    /// is between 1 and XLEN.
+    /// ```rust
+    /// use rysk::Xlen;
+    /// assert_eq!(u32::zero_extend(0xFFFF_FFFF, 1), 1);
+    /// assert_eq!(u32::zero_extend(0xFFFF_FFFF, 32), 0xFFFF_FFFF);
+    /// assert_eq!(u64::zero_extend(0xFFFF_FFFF_8000_0000, 32), 0x8000_0000);
+    /// assert_eq!(u64::zero_extend(u64::MAX, 64), u64::MAX);
+    /// ```
+    fn zero_extend(value: Self, bits: u32) -> Self;
 }
 impl Xlen for u32 {
     const BITS: usize = Self::BITS as _;
     const MAX: usize = Self::MAX as _;
+    #[inline]
+    fn to_u64(self) -> u64 {
+        self as u64
+    }
+    #[inline]
+    fn from_u64(value: u64) -> Self {
+        value as Self
+    }
+    #[inline]
+    fn sign_extend(value: Self, bits: u32) -> Self {
+        let shift = Self::BITS - bits;
+        (((value << shift) as i32) >> shift) as Self
+    }
+    #[inline]
+    fn zero_extend(value: Self, bits: u32) -> Self {
+        let shift = Self::BITS - bits;
+        (value << shift) >> shift
+    }
 }
 impl Xlen for u64 {
     const BITS: usize = Self::BITS as _;
     const MAX: usize = Self::MAX as _;
+    #[inline]
+    fn to_u64(self) -> u64 {
+        self
+    }
+    #[inline]
+    fn from_u64(value: u64) -> Self {
+        value
+    }
+    #[inline]
+    fn sign_extend(value: Self, bits: u32) -> Self {
+        let shift = Self::BITS - bits;
+        (((value << shift) as i64) >> shift) as Self
+    }
+    #[inline]
+    fn zero_extend(value: Self, bits: u32) -> Self {
+        let shift = Self::BITS - bits;
+        (value << shift) >> shift
+    }
 }
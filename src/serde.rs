@@ -0,0 +1,112 @@
+//! Serde support for the core types, enabled by the `serde` feature.
+//!
+//! [`Instruction`] is serialized as its raw value and [`Cause`] as its raw
+//! `mcause` value, so both are lossless in every format. Registers are
+//! serialized by ABI name in human-readable formats and by number otherwise.
+//! ```rust
+//! use rysk::{Cause, FRegister, Instruction, Register};
+//! use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Compact, Configure, Readable, Token};
+//!
+//! // addi a0,a0,1
+//! let addi = Instruction::new(0x00150513);
+//! assert_tokens(&addi, &[Token::U32(0x00150513)]);
+//!
+//! assert_tokens(&Register::X10.readable(), &[Token::Str("a0")]);
+//! assert_tokens(&Register::X10.compact(), &[Token::U8(10)]);
+//! assert_tokens(&FRegister::F8.readable(), &[Token::Str("fs0")]);
+//! assert_tokens(&FRegister::F8.compact(), &[Token::U8(8)]);
+//! // Any accepted register name deserializes.
+//! assert_de_tokens(&Register::X8.readable(), &[Token::Str("fp")]);
+//! assert_de_tokens(&FRegister::F31.readable(), &[Token::Str("f31")]);
+//! assert_de_tokens_error::<Compact<Register>>(&[Token::U8(32)], "invalid value: integer `32`, expected a register");
+//! assert_de_tokens_error::<Readable<Register>>(&[Token::Str("x32")], "invalid value: string \"x32\", expected a register");
+//!
+//! assert_tokens(&Cause::<u32>::ILLEGAL_INSTRUCTION, &[Token::U64(2)]);
+//! assert_tokens(&Cause::<u32>::MACHINE_TIMER, &[Token::U64(0x8000_0007)]);
+//! assert_tokens(&Cause::<u64>::MACHINE_TIMER, &[Token::U64(0x8000_0000_0000_0007)]);
+//! assert_de_tokens_error::<Cause<u32>>(&[Token::U64(1 << 32)], "invalid value: integer `4294967296`, expected an XLEN cause");
+//! ```
+use core::{fmt, marker::PhantomData};
+
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Cause, FRegister, Instruction, Register, Xlen};
+
+impl Serialize for Instruction {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.0)
+    }
+}
+impl<'de> Deserialize<'de> for Instruction {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u32::deserialize(deserializer).map(Instruction)
+    }
+}
+
+macro_rules! register {
+    ($($ty:ident: $expecting:literal;)*) => {$(
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    serializer.serialize_str(self.abi_name())
+                } else {
+                    serializer.serialize_u8(*self as u8)
+                }
+            }
+        }
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+                impl de::Visitor<'_> for Visitor {
+                    type Value = $ty;
+                    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                        f.write_str($expecting)
+                    }
+                    fn visit_u64<E: de::Error>(self, v: u64) -> Result<$ty, E> {
+                        u8::try_from(v).ok()
+                            .and_then($ty::new)
+                            .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+                    }
+                    fn visit_str<E: de::Error>(self, v: &str) -> Result<$ty, E> {
+                        $ty::from_name(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+                    }
+                }
+                if deserializer.is_human_readable() {
+                    deserializer.deserialize_str(Visitor)
+                } else {
+                    deserializer.deserialize_u8(Visitor)
+                }
+            }
+        }
+    )*};
+}
+register! {
+    Register: "a register";
+    FRegister: "a floating-point register";
+}
+
+impl<X: Xlen> Serialize for Cause<X> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let interrupt = (self.interrupt() as u64) << (X::BITS - 1);
+        serializer.serialize_u64(self.code().to_u64() | interrupt)
+    }
+}
+impl<'de, X: Xlen> Deserialize<'de> for Cause<X> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<X>(PhantomData<X>);
+        impl<X: Xlen> de::Visitor<'_> for Visitor<X> {
+            type Value = Cause<X>;
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an XLEN cause")
+            }
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Cause<X>, E> {
+                if X::from_u64(v).to_u64() != v {
+                    return Err(E::invalid_value(de::Unexpected::Unsigned(v), &self));
+                }
+                let interrupt = (v >> (X::BITS - 1)) & 1 != 0;
+                Ok(Cause::new(X::from_u64(v), interrupt))
+            }
+        }
+        deserializer.deserialize_u64(Visitor(PhantomData))
+    }
+}
@@ -0,0 +1,230 @@
+//! A compact binary format for per-instruction execution traces.
+//!
+//! Each record starts with an unsigned LEB128 varint holding the zigzag
+//! encoded pc delta from the previous record shifted left by one, with the
+//! low bit set if a register writeback follows. The instruction follows as 2
+//! or 4 little-endian bytes depending on its length. A writeback is encoded as
+//! the register number byte followed by the value as a varint.
+//!
+//! The first record's delta is relative to a pc of zero.
+//! ```rust
+//! use rysk::{Instruction, Register, trace::{TraceReader, TraceWriter}};
+//!
+//! let mut writer = TraceWriter::new(Vec::new());
+//! // addi a0,a0,1
+//! writer.record(0x8000_0000u32, Instruction::new(0x00150513), Some((Register::X10, 1))).unwrap();
+//! // c.addi a0,-1
+//! writer.record(0x8000_0004, Instruction::from_parcels([0x157D, 0]).0, None).unwrap();
+//! let trace = writer.into_inner();
+//!
+//! let mut reader = TraceReader::<_, u32>::new(trace.as_slice());
+//! let first = reader.read().unwrap().unwrap();
+//! assert_eq!(first.pc, 0x8000_0000);
+//! assert_eq!(first.instruction.rd(), Register::X10);
+//! assert_eq!(first.writeback, Some((Register::X10, 1)));
+//! let second = reader.read().unwrap().unwrap();
+//! assert_eq!(second.pc, 0x8000_0004);
+//! assert!(second.instruction.compressed());
+//! assert_eq!(second.writeback, None);
+//! assert!(reader.read().unwrap().is_none());
+//!
+//! // A record cut short before its writeback value is an error.
+//! let mut reader = TraceReader::<_, u32>::new(&trace[..10]);
+//! assert_eq!(reader.read().unwrap_err().kind(), std::io::ErrorKind::UnexpectedEof);
+//! ```
+
+use std::io::{self, Read, Write};
+
+use crate::{Instruction, Register, Xlen};
+
+/// A single retired instruction.
+#[derive(Clone, Copy, Debug)]
+pub struct TraceRecord<X: Xlen> {
+    /// Address of the instruction.
+    pub pc: X,
+    /// The retired instruction, holding only its parcel if compressed.
+    pub instruction: Instruction,
+    /// The destination register and the value written to it, if recorded.
+    pub writeback: Option<(Register, X)>
+}
+
+/// Streams trace records to a [`Write`] sink.
+pub struct TraceWriter<W: Write, X: Xlen> {
+    inner: W,
+    pc: X
+}
+impl<W: Write, X: Xlen> TraceWriter<W, X> {
+    /// Create a writer appending records to `inner`.
+    ///
+    /// A hart can record every retired instruction from
+    /// [`Hart::on_retire`](crate::Hart::on_retire).
+    /// ```rust
+    /// use rysk::{Cause, Hart, Instruction, Register, env::{BasicHart, Memory, Privilege}};
+    /// use rysk::trace::{TraceReader, TraceWriter};
+    ///
+    /// struct Tracing { hart: BasicHart<u32, Memory<u32>>, trace: TraceWriter<Vec<u8>, u32> }
+    /// impl Hart<u32, Memory<u32>> for Tracing {
+    ///     fn load_register(&self, r: Register) -> u32 { self.hart.load_register(r) }
+    ///     fn store_register(&mut self, r: Register, v: u32) { self.hart.store_register(r, v) }
+    ///     fn bus(&mut self) -> &mut Memory<u32> { self.hart.bus() }
+    ///     fn bus_ref(&self) -> &Memory<u32> { self.hart.bus_ref() }
+    ///     fn pc(&self) -> u32 { self.hart.pc() }
+    ///     fn set_pc(&mut self, pc: u32) { self.hart.set_pc(pc) }
+    ///     fn privilege(&self) -> Privilege { self.hart.privilege() }
+    ///     fn set_privilege(&mut self, p: Privilege) { self.hart.set_privilege(p) }
+    ///     fn on_retire(&mut self, pc: u32, instruction: Instruction) {
+    ///         let rd = instruction.expand_rv32().unwrap().rd();
+    ///         let writeback = Some((rd, self.get_reg(rd)));
+    ///         self.trace.record(pc, instruction, writeback).unwrap();
+    ///     }
+    /// }
+    ///
+    /// // c.li a0,1; addi a0,a0,2; ebreak
+    /// let memory = Memory::from_vec(0x1000, vec![0x05, 0x45, 0x13, 0x05, 0x25, 0x00, 0x73, 0x00, 0x10, 0x00]);
+    /// let mut hart = Tracing { hart: BasicHart::new(memory, 0x1000), trace: TraceWriter::new(Vec::new()) };
+    /// let (retired, trap) = hart.run(10);
+    /// assert_eq!(retired, 2);
+    /// assert!(trap.map(|trap| trap.cause) == Some(Cause::BREAKPOINT));
+    ///
+    /// let trace = hart.trace.into_inner();
+    /// let mut reader = TraceReader::<_, u32>::new(trace.as_slice());
+    /// let first = reader.read().unwrap().unwrap();
+    /// assert_eq!((first.pc, first.writeback), (0x1000, Some((Register::X10, 1))));
+    /// let second = reader.read().unwrap().unwrap();
+    /// assert_eq!((second.pc, second.writeback), (0x1002, Some((Register::X10, 3))));
+    /// assert!(reader.read().unwrap().is_none());
+    /// ```
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pc: X::from_u64(0)
+        }
+    }
+    /// Append a record for the instruction retired at `pc`.
+    pub fn record(&mut self, pc: X, instruction: Instruction, writeback: Option<(Register, X)>) -> io::Result<()> {
+        let delta = sign_extend::<X>(pc.to_u64().wrapping_sub(self.pc.to_u64()));
+        let zigzag = ((delta << 1) ^ (delta >> 63)) as u64 as u128;
+        self.pc = pc;
+        write_varint(&mut self.inner, (zigzag << 1) | writeback.is_some() as u128)?;
+        if instruction.compressed() {
+            self.inner.write_all(&(instruction.0 as u16).to_le_bytes())?;
+        } else {
+            self.inner.write_all(&instruction.0.to_le_bytes())?;
+        }
+        if let Some((r, value)) = writeback {
+            self.inner.write_all(&[r as u8])?;
+            write_varint(&mut self.inner, value.to_u64() as u128)?;
+        }
+        Ok(())
+    }
+    /// Returns a reference to the underlying sink.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+    /// Unwrap the writer, returning the underlying sink.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// Reads trace records written by a [`TraceWriter`].
+pub struct TraceReader<R: Read, X: Xlen> {
+    inner: R,
+    pc: X
+}
+impl<R: Read, X: Xlen> TraceReader<R, X> {
+    /// Create a reader decoding records from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pc: X::from_u64(0)
+        }
+    }
+    /// Read the next record, returning [`None`] at the end of the trace.
+    pub fn read(&mut self) -> io::Result<Option<TraceRecord<X>>> {
+        let header = match read_varint(&mut self.inner)? {
+            Some(header) => header,
+            None => return Ok(None)
+        };
+        let zigzag = (header >> 1) as u64;
+        let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+        let pc = X::from_u64(self.pc.to_u64().wrapping_add(delta as u64));
+        self.pc = pc;
+
+        let mut parcels = [0; 2];
+        parcels[0] = u16::from_le_bytes(read_array(&mut self.inner)?);
+        if parcels[0] & 0b11 == 0b11 {
+            parcels[1] = u16::from_le_bytes(read_array(&mut self.inner)?);
+        }
+        let (instruction, _) = Instruction::from_parcels(parcels);
+
+        let writeback = if header & 1 != 0 {
+            let [r] = read_array(&mut self.inner)?;
+            let r = Register::new(r).ok_or_else(|| invalid("invalid writeback register"))?;
+            let value = read_varint_required(&mut self.inner)?;
+            Some((r, X::from_u64(value as u64)))
+        } else {
+            None
+        };
+        Ok(Some(TraceRecord { pc, instruction, writeback }))
+    }
+    /// Unwrap the reader, returning the underlying source.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+/// Sign-extend an XLEN-bit difference to 64 bits.
+#[inline]
+fn sign_extend<X: Xlen>(value: u64) -> i64 {
+    let shift = 64 - X::BITS as u32;
+    ((value << shift) as i64) >> shift
+}
+fn invalid(message: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+fn write_varint<W: Write>(w: &mut W, mut value: u128) -> io::Result<()> {
+    let mut buf = [0; 19];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break w.write_all(&buf[..len]);
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+}
+/// Read a varint, returning [`None`] if the source is exhausted before the
+/// first byte.
+fn read_varint<R: Read>(r: &mut R) -> io::Result<Option<u128>> {
+    let mut value = 0;
+    for i in 0..19 {
+        let mut byte = [0];
+        if r.read(&mut byte)? == 0 {
+            return if i == 0 {
+                Ok(None)
+            } else {
+                Err(io::ErrorKind::UnexpectedEof.into())
+            };
+        }
+        value |= ((byte[0] & 0x7F) as u128) << (7 * i);
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(invalid("varint is too long"))
+}
+/// Read a varint that must be present, treating an exhausted source as an
+/// unexpected end of file.
+fn read_varint_required<R: Read>(r: &mut R) -> io::Result<u128> {
+    read_varint(r)?.ok_or_else(|| io::ErrorKind::UnexpectedEof.into())
+}
+fn read_array<R: Read, const N: usize>(r: &mut R) -> io::Result<[u8; N]> {
+    let mut buf = [0; N];
+    r.read_exact(&mut buf)?;
+    Ok(buf)
+}